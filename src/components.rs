@@ -1,15 +1,38 @@
 use bevy_rapier2d::prelude::*;
-use std::marker::PhantomData;
+use std::{f32::consts::FRAC_PI_2, marker::PhantomData};
 use rand::{prelude::*, distributions::Standard};
 
 use bevy::prelude::*;
 
+use crate::{
+    consts::{
+        ENEMY_GROUPS, GHOST_GROUPS, MELEE_ARC_SLACK, RADIUS_PER_PARTY_MEMBER, VETERAN_KILLS,
+    },
+    resources::{
+        ALCHEMIST_ANIMATIONS, ARCHER_ANIMATIONS, BAT_ANIMATIONS, CYCLOPS_ANIMATIONS,
+        DWARF_ANIMATIONS, EVIL_WIZARD_ANIMATIONS, GHOST_ANIMATIONS, KNIGHT_ANIMATIONS,
+        LOBSTER_ANIMATIONS, PLAYER_ANIMATIONS, RAT_ANIMATIONS, SPIDER_ANIMATIONS,
+        WIZARD_ANIMATIONS,
+    },
+};
+
 #[derive(Component, Default)]
 pub struct Health(pub f32, pub f32);
 
 #[derive(Component)]
 pub struct IsDead;
 
+#[derive(Component, Deref, DerefMut)]
+pub struct Corpse(pub Timer);
+
+#[derive(Component, Default)]
+pub struct Revivable {
+    pub progress: f32,
+}
+
+#[derive(Component)]
+pub struct ReviveMarker;
+
 #[derive(Component)]
 pub struct HasHealthBar;
 
@@ -19,20 +42,146 @@ pub struct MainHealthBar;
 #[derive(Component)]
 pub struct InParty;
 
-#[derive(Component, Default, Deref, DerefMut)]
-pub struct IndicatorEntity(pub Option<Entity>);
+#[derive(Component, Deref, DerefMut)]
+pub struct PinnedOffset(pub Vec2);
+
+#[derive(Component)]
+pub struct MainCamera;
+
+#[derive(Component)]
+pub struct CameraController {
+    pub focus: Vec2,
+    pub zoom: f32,
+    pub trauma: f32,
+}
+
+#[derive(Component)]
+pub struct FocusTarget;
+
+#[derive(Component)]
+pub struct Reticle;
+
+#[derive(Component)]
+pub struct ColliderOutline;
 
 #[derive(Component)]
-pub struct Indicator;
+pub struct Wall;
+
+#[derive(Component)]
+pub struct Indicator(pub Entity);
 
 #[derive(Component, Default)]
 pub struct PartyRadius(pub f32);
 
+#[derive(Component)]
+pub struct PartyCircle;
+
+impl PartyRadius {
+    pub fn capacity(&self) -> usize {
+        (self.0 / RADIUS_PER_PARTY_MEMBER) as usize
+    }
+}
+
+#[derive(Component, Deref, DerefMut)]
+pub struct Heading(pub Vec2);
+
+impl Default for Heading {
+    fn default() -> Self {
+        Self(Vec2::X)
+    }
+}
+
 #[derive(Component, Deref, DerefMut, Default)]
 pub struct AttackTimer(pub Timer);
 #[derive(Component, Deref, DerefMut, Default)]
 pub struct AnimationTimer(pub Timer);
 
+#[derive(Clone, Copy)]
+pub struct Clip {
+    pub first: usize,
+    pub last: usize,
+    pub frame_time: f32,
+    pub looping: bool,
+}
+
+pub struct AnimationSet {
+    pub idle: Clip,
+    pub walk: Clip,
+    pub attack: Clip,
+    pub hurt: Clip,
+    pub die: Clip,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum AnimationState {
+    Idle,
+    Walk,
+    Attack,
+    Hurt,
+    Die,
+}
+
+#[derive(Component)]
+pub struct AnimationController {
+    pub set: &'static AnimationSet,
+    pub state: AnimationState,
+    pub timer: Timer,
+    pub frame: usize,
+    pub finished: bool,
+}
+
+impl AnimationController {
+    pub fn new(set: &'static AnimationSet) -> Self {
+        Self {
+            set,
+            state: AnimationState::Idle,
+            timer: Timer::from_seconds(set.idle.frame_time, true),
+            frame: set.idle.first,
+            finished: false,
+        }
+    }
+
+    pub fn clip(&self) -> Clip {
+        match self.state {
+            AnimationState::Idle => self.set.idle,
+            AnimationState::Walk => self.set.walk,
+            AnimationState::Attack => self.set.attack,
+            AnimationState::Hurt => self.set.hurt,
+            AnimationState::Die => self.set.die,
+        }
+    }
+
+    pub fn play(&mut self, state: AnimationState) {
+        if self.state == AnimationState::Die {
+            return;
+        }
+        self.state = state;
+        let clip = self.clip();
+        self.timer = Timer::from_seconds(clip.frame_time, true);
+        self.frame = clip.first;
+        self.finished = false;
+    }
+
+    pub fn is_busy(&self) -> bool {
+        matches!(self.state, AnimationState::Attack | AnimationState::Hurt) && !self.finished
+    }
+}
+
+#[derive(Component, Deref, DerefMut)]
+pub struct DashCooldown(pub Timer);
+
+#[derive(Component, Deref, DerefMut)]
+pub struct RallyCooldown(pub Timer);
+
+#[derive(Component, Deref, DerefMut)]
+pub struct Dashing(pub Timer);
+
+#[derive(Component, Deref, DerefMut)]
+pub struct Rallied(pub Timer);
+
+#[derive(Component)]
+pub struct Invulnerable;
+
 #[derive(Component, Deref, DerefMut, Default)]
 pub struct AttackRange(pub f32);
 #[derive(Component, Clone, Copy, Deref, DerefMut, Default)]
@@ -46,6 +195,133 @@ pub struct Projectile<C: Component>(pub bool, pub PhantomData<C>);
 #[derive(Component, Deref, DerefMut, Default)]
 pub struct Speed(pub f32);
 
+#[derive(Component)]
+pub struct WindUp {
+    pub timer: Timer,
+    pub damage: Damage,
+    pub knockback: Knockback,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ProjectileType {
+    Arrow,
+    Fireball,
+    Flask,
+    Slash,
+    EnemySlash,
+}
+
+impl ProjectileType {
+    pub fn collider(&self) -> Collider {
+        match self {
+            ProjectileType::Arrow => Collider::cuboid(1.5, 6.0),
+            ProjectileType::Fireball => Collider::ball(3.0),
+            ProjectileType::Flask => Collider::ball(2.5),
+            ProjectileType::Slash => Collider::cuboid(5.0, 2.0),
+            ProjectileType::EnemySlash => Collider::ball(5.5),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct MeleeArc {
+    pub angle: f32,
+    pub reach: f32,
+}
+
+impl MeleeArc {
+    // Reach past the attack range so the cone always covers the target's collider
+    pub fn new(angle: f32, attack_range: f32) -> Self {
+        Self {
+            angle,
+            reach: attack_range + MELEE_ARC_SLACK,
+        }
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.reach / 16.0
+    }
+
+    // Cone in the slash sprite's local space, pointing down -Y from the attacker
+    pub fn collider(&self) -> Collider {
+        let reach = self.reach / self.scale();
+        let apex = Vec2::new(0.0, reach / 2.0);
+        let mut points = vec![apex];
+        points.extend((0..=6).map(|i| {
+            let t = -self.angle / 2.0 + self.angle * i as f32 / 6.0;
+            apex + Vec2::from_angle(-FRAC_PI_2 + t) * reach
+        }));
+        Collider::convex_hull(&points).unwrap()
+    }
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DamageKind {
+    #[default]
+    Physical,
+    Fire,
+    Poison,
+}
+
+impl DamageKind {
+    pub fn color(&self) -> Color {
+        match self {
+            DamageKind::Physical => Color::WHITE,
+            DamageKind::Fire => Color::ORANGE,
+            DamageKind::Poison => Color::LIME_GREEN,
+        }
+    }
+}
+
+/// Speed in px/s a hit adds to its target, away from the attacker.
+#[derive(Component, Clone, Copy, Deref, DerefMut)]
+pub struct Knockback(pub f32);
+
+#[derive(Component, Deref, DerefMut)]
+pub struct Owner(pub Entity);
+
+#[derive(Component)]
+pub struct LastHitBy(pub Entity, pub Option<EnemyType>);
+
+#[derive(Component, Deref, DerefMut, Default)]
+pub struct Kills(pub u32);
+
+#[derive(Component, Deref, DerefMut, Default)]
+pub struct DamageDealt(pub f32);
+
+#[derive(Component)]
+pub struct RosterHighlight;
+
+#[derive(Component, Deref, DerefMut)]
+pub struct Piercing(pub u32);
+
+#[derive(Component)]
+pub struct Homing {
+    pub target: Entity,
+    pub turn_rate: f32,
+}
+
+#[derive(Component, Clone, Copy)]
+pub struct Splash {
+    pub radius: f32,
+}
+
+#[derive(Component)]
+pub struct Chaining {
+    pub remaining: u32,
+    pub range: f32,
+    pub hit: Vec<Entity>,
+}
+
+#[derive(Component, Deref, DerefMut)]
+pub struct Explosion(pub Timer);
+
+#[derive(Component, Deref, DerefMut)]
+pub struct HitFlash(pub Timer);
+
+#[derive(Component, Deref, DerefMut)]
+pub struct DamageNumber(pub Timer);
+
 #[derive(Component)]
 pub struct Sound(pub Handle<AudioSource>);
 
@@ -60,18 +336,36 @@ pub enum EnemyType {
     Spider,
 }
 
+pub struct UnitStats {
+    pub health: f32,
+    pub damage: f32,
+    pub attack_range: f32,
+    pub attack_cooldown: f32,
+}
+
+fn weighted_choice<T: Copy, R: Rng + ?Sized>(weights: &[(T, u32)], rng: &mut R) -> T {
+    let total = weights.iter().map(|(_, weight)| weight).sum();
+    let mut val = rng.gen_range(0..total);
+    for &(choice, weight) in weights {
+        if val < weight {
+            return choice;
+        }
+        val -= weight;
+    }
+    unreachable!()
+}
+
+fn spawn_chance<T: PartialEq>(weights: &[(T, u32)], choice: &T) -> f32 {
+    let total: u32 = weights.iter().map(|(_, weight)| weight).sum();
+    weights
+        .iter()
+        .find(|(other, _)| other == choice)
+        .map_or(0.0, |(_, weight)| *weight as f32 / total as f32)
+}
+
 impl Distribution<EnemyType> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> EnemyType {
-        let val = rng.gen_range(0..16);
-        match val {
-            0..=3 => EnemyType::Bat,
-            4..=7 => EnemyType::Rat,
-            8..=10 => EnemyType::Spider,
-            11..=13 => EnemyType::Lobster,
-            14 => EnemyType::Ghost,
-            15 => EnemyType::EvilWizard,
-            _ => unreachable!()
-        }
+        weighted_choice(&EnemyType::SPAWN_WEIGHTS, rng)
     }
 }
 
@@ -87,17 +381,409 @@ pub enum AllyType {
     Player,
 }
 
+impl EnemyType {
+    pub const SPAWN_WEIGHTS: [(EnemyType, u32); 6] = [
+        (EnemyType::Bat, 4),
+        (EnemyType::Rat, 4),
+        (EnemyType::Spider, 3),
+        (EnemyType::Lobster, 3),
+        (EnemyType::Ghost, 1),
+        (EnemyType::EvilWizard, 1),
+    ];
+
+    pub fn spawn_chance(&self) -> f32 {
+        spawn_chance(&EnemyType::SPAWN_WEIGHTS, self)
+    }
+
+    pub fn stats(&self) -> UnitStats {
+        match self {
+            EnemyType::Bat => UnitStats {
+                health: 60.0,
+                damage: 8.0,
+                attack_range: 45.0,
+                attack_cooldown: 0.75,
+            },
+            EnemyType::EvilWizard => UnitStats {
+                health: 75.0,
+                damage: 15.0,
+                attack_range: 200.0,
+                attack_cooldown: 1.25,
+            },
+            EnemyType::Ghost => UnitStats {
+                health: 100.0,
+                damage: 20.0,
+                attack_range: 60.0,
+                attack_cooldown: 1.0,
+            },
+            EnemyType::Lobster => UnitStats {
+                health: 80.0,
+                damage: 15.0,
+                attack_range: 40.0,
+                attack_cooldown: 1.0,
+            },
+            EnemyType::Rat => UnitStats {
+                health: 50.0,
+                damage: 5.0,
+                attack_range: 50.0,
+                attack_cooldown: 0.5,
+            },
+            EnemyType::Spider => UnitStats {
+                health: 65.0,
+                damage: 10.0,
+                attack_range: 40.0,
+                attack_cooldown: 0.75,
+            },
+        }
+    }
+
+    pub fn is_ranged(&self) -> bool {
+        matches!(self, EnemyType::EvilWizard)
+    }
+
+    /// Radians per second its projectiles turn towards their target.
+    pub fn homing_turn_rate(&self) -> Option<f32> {
+        match self {
+            EnemyType::EvilWizard => Some(1.5),
+            _ => None,
+        }
+    }
+
+    pub fn speed(&self) -> f32 {
+        match self {
+            EnemyType::Bat => 90.0,
+            EnemyType::EvilWizard => 70.0,
+            EnemyType::Ghost => 65.0,
+            EnemyType::Lobster => 75.0,
+            EnemyType::Rat => 110.0,
+            EnemyType::Spider => 150.0,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            EnemyType::Bat => "Lives in the rafters and hates visitors",
+            EnemyType::EvilWizard => "Went to the same academy, graduated differently",
+            EnemyType::Ghost => "Whoever it was, it isn't happy now",
+            EnemyType::Lobster => "Far from the sea and cranky about it",
+            EnemyType::Rat => "There's never just one",
+            EnemyType::Spider => "Eight legs, all of them in a hurry",
+        }
+    }
+
+    pub fn animations(&self) -> &'static AnimationSet {
+        match self {
+            EnemyType::Bat => &BAT_ANIMATIONS,
+            EnemyType::EvilWizard => &EVIL_WIZARD_ANIMATIONS,
+            EnemyType::Ghost => &GHOST_ANIMATIONS,
+            EnemyType::Lobster => &LOBSTER_ANIMATIONS,
+            EnemyType::Rat => &RAT_ANIMATIONS,
+            EnemyType::Spider => &SPIDER_ANIMATIONS,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EnemyType::Bat => "Bat",
+            EnemyType::EvilWizard => "Evil Wizard",
+            EnemyType::Ghost => "Ghost",
+            EnemyType::Lobster => "Lobster",
+            EnemyType::Rat => "Rat",
+            EnemyType::Spider => "Spider",
+        }
+    }
+
+    pub fn collider(&self) -> Collider {
+        match self {
+            EnemyType::Bat => Collider::ball(4.0),
+            EnemyType::Rat => Collider::ball(4.5),
+            EnemyType::Spider => Collider::ball(6.0),
+            EnemyType::Lobster => Collider::capsule_x(2.5, 5.5),
+            EnemyType::Ghost | EnemyType::EvilWizard => Collider::capsule_y(3.0, 5.0),
+        }
+    }
+
+    pub fn collision_groups(&self) -> CollisionGroups {
+        match self {
+            EnemyType::Ghost => GHOST_GROUPS,
+            _ => ENEMY_GROUPS,
+        }
+    }
+
+    pub fn density(&self) -> f32 {
+        match self {
+            EnemyType::Bat => 0.4,
+            EnemyType::Rat => 0.6,
+            EnemyType::Ghost => 0.5,
+            EnemyType::Spider => 0.8,
+            EnemyType::EvilWizard => 1.0,
+            EnemyType::Lobster => 2.0,
+        }
+    }
+
+    pub fn knockback(&self) -> f32 {
+        match self {
+            EnemyType::Lobster => 96.0,
+            EnemyType::EvilWizard => 64.0,
+            _ => 32.0,
+        }
+    }
+}
+
 impl Distribution<AllyType> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> AllyType {
-        let val = rng.gen_range(0..17);
-        match val {
-            0..=4 => AllyType::Alchemist,
-            5..=7 => AllyType::Dwarf,
-            8..=9 => AllyType::Knight,
-            10..=12 => AllyType::Cyclops,
-            13..=15 => AllyType::Archer,
-            16 => AllyType::Wizard,
-            _ => unreachable!()
+        weighted_choice(&AllyType::SPAWN_WEIGHTS, rng)
+    }
+}
+
+impl AllyType {
+    pub const SPAWN_WEIGHTS: [(AllyType, u32); 6] = [
+        (AllyType::Alchemist, 5),
+        (AllyType::Dwarf, 3),
+        (AllyType::Knight, 2),
+        (AllyType::Cyclops, 3),
+        (AllyType::Archer, 3),
+        (AllyType::Wizard, 1),
+    ];
+
+    pub fn spawn_chance(&self) -> f32 {
+        spawn_chance(&AllyType::SPAWN_WEIGHTS, self)
+    }
+
+    pub fn animations(&self) -> &'static AnimationSet {
+        match self {
+            AllyType::Alchemist => &ALCHEMIST_ANIMATIONS,
+            AllyType::Archer => &ARCHER_ANIMATIONS,
+            AllyType::Cyclops => &CYCLOPS_ANIMATIONS,
+            AllyType::Dwarf => &DWARF_ANIMATIONS,
+            AllyType::Knight => &KNIGHT_ANIMATIONS,
+            AllyType::Wizard => &WIZARD_ANIMATIONS,
+            AllyType::Player => &PLAYER_ANIMATIONS,
+        }
+    }
+
+    pub fn revives(&self) -> bool {
+        matches!(self, AllyType::Alchemist)
+    }
+
+    pub fn stats(&self) -> Option<UnitStats> {
+        match self {
+            AllyType::Alchemist => Some(UnitStats {
+                health: 100.0,
+                damage: 15.0,
+                attack_range: 80.0,
+                attack_cooldown: 1.0,
+            }),
+            AllyType::Archer => Some(UnitStats {
+                health: 100.0,
+                damage: 15.0,
+                attack_range: 250.0,
+                attack_cooldown: 0.5,
+            }),
+            AllyType::Cyclops => Some(UnitStats {
+                health: 150.0,
+                damage: 35.0,
+                attack_range: 60.0,
+                attack_cooldown: 1.5,
+            }),
+            AllyType::Dwarf => Some(UnitStats {
+                health: 90.0,
+                damage: 25.0,
+                attack_range: 90.0,
+                attack_cooldown: 1.0,
+            }),
+            AllyType::Knight => Some(UnitStats {
+                health: 115.0,
+                damage: 25.0,
+                attack_range: 80.0,
+                attack_cooldown: 0.75,
+            }),
+            AllyType::Wizard => Some(UnitStats {
+                health: 75.0,
+                damage: 30.0,
+                attack_range: 200.0,
+                attack_cooldown: 1.25,
+            }),
+            AllyType::Player => None,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            AllyType::Alchemist => "Never goes anywhere without a satchel of flasks",
+            AllyType::Archer => "Keen-eyed and quick on the draw",
+            AllyType::Cyclops => "One eye, one club, no patience",
+            AllyType::Dwarf => "Short, stout and stubborn",
+            AllyType::Knight => "Sworn to protect whoever recruits them",
+            AllyType::Wizard => "Bookish, fragile and very flammable",
+            AllyType::Player => "That's you",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AllyType::Alchemist => "Alchemist",
+            AllyType::Archer => "Archer",
+            AllyType::Cyclops => "Cyclops",
+            AllyType::Dwarf => "Dwarf",
+            AllyType::Knight => "Knight",
+            AllyType::Wizard => "Wizard",
+            AllyType::Player => "Player",
+        }
+    }
+
+    pub fn is_ranged(&self) -> bool {
+        matches!(
+            self,
+            AllyType::Alchemist | AllyType::Archer | AllyType::Wizard
+        )
+    }
+
+    pub fn collider(&self) -> Collider {
+        match self {
+            AllyType::Cyclops => Collider::capsule_y(3.0, 6.5),
+            AllyType::Dwarf => Collider::capsule_y(2.0, 5.5),
+            _ => Collider::capsule_y(3.0, 5.0),
+        }
+    }
+
+    pub fn density(&self) -> f32 {
+        match self {
+            AllyType::Alchemist | AllyType::Archer | AllyType::Wizard => 0.8,
+            AllyType::Dwarf => 1.4,
+            AllyType::Knight => 1.6,
+            AllyType::Cyclops => 3.0,
+            AllyType::Player => 1.2,
+        }
+    }
+
+    pub fn melee_arc(&self, attack_range: f32) -> Option<MeleeArc> {
+        match self {
+            AllyType::Cyclops => Some(MeleeArc::new(2.0, attack_range)),
+            AllyType::Knight => Some(MeleeArc::new(1.4, attack_range)),
+            _ => None,
+        }
+    }
+
+    pub fn splash(&self) -> Option<Splash> {
+        match self {
+            AllyType::Wizard => Some(Splash { radius: 30.0 }),
+            _ => None,
+        }
+    }
+
+    /// How many extra enemies each arrow passes through, which grows with experience.
+    pub fn piercing(&self, kills: u32) -> u32 {
+        match self {
+            AllyType::Archer if kills >= VETERAN_KILLS => 2,
+            _ => 0,
+        }
+    }
+
+    pub fn knockback(&self) -> f32 {
+        match self {
+            AllyType::Cyclops => 120.0,
+            AllyType::Knight | AllyType::Dwarf => 80.0,
+            AllyType::Wizard => 64.0,
+            _ => 32.0,
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlayerClass {
+    #[default]
+    Knight,
+    Archer,
+    Alchemist,
+}
+
+pub struct ClassStats {
+    pub health: f32,
+    pub damage: f32,
+    pub attack_range: f32,
+    pub attack_cooldown: f32,
+    pub party_radius: f32,
+}
+
+impl PlayerClass {
+    pub const ALL: [PlayerClass; 3] = [
+        PlayerClass::Knight,
+        PlayerClass::Archer,
+        PlayerClass::Alchemist,
+    ];
+
+    pub fn melee_arc(&self, attack_range: f32) -> Option<MeleeArc> {
+        match self {
+            PlayerClass::Knight => Some(MeleeArc::new(1.6, attack_range)),
+            _ => None,
+        }
+    }
+
+    pub fn piercing(&self) -> u32 {
+        match self {
+            PlayerClass::Archer => 2,
+            _ => 0,
+        }
+    }
+
+    pub fn chaining(&self) -> Option<Chaining> {
+        match self {
+            PlayerClass::Alchemist => Some(Chaining {
+                remaining: 2,
+                range: 80.0,
+                hit: Vec::new(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Health restored to the most wounded party member on every attack.
+    pub fn heal(&self) -> Option<f32> {
+        match self {
+            PlayerClass::Alchemist => Some(10.0),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlayerClass::Knight => "Knight",
+            PlayerClass::Archer => "Archer",
+            PlayerClass::Alchemist => "Alchemist",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            PlayerClass::Knight => "Sturdy frontliner\nthat slashes up close",
+            PlayerClass::Archer => "Fragile marksman\nthat shoots from afar",
+            PlayerClass::Alchemist => "Throws flasks that\nheal wounded allies",
+        }
+    }
+
+    pub fn stats(&self) -> ClassStats {
+        match self {
+            PlayerClass::Knight => ClassStats {
+                health: 150.0,
+                damage: 15.0,
+                attack_range: 60.0,
+                attack_cooldown: 0.5,
+                party_radius: 40.0,
+            },
+            PlayerClass::Archer => ClassStats {
+                health: 80.0,
+                damage: 12.0,
+                attack_range: 220.0,
+                attack_cooldown: 0.6,
+                party_radius: 35.0,
+            },
+            PlayerClass::Alchemist => ClassStats {
+                health: 100.0,
+                damage: 8.0,
+                attack_range: 150.0,
+                attack_cooldown: 0.8,
+                party_radius: 50.0,
+            },
         }
     }
 }
@@ -105,6 +791,8 @@ impl Distribution<AllyType> for Standard {
 #[derive(Bundle, Default)]
 pub struct PlayerBundle {
     pub party_radius: PartyRadius,
+    pub class: PlayerClass,
+    pub heading: Heading,
     pub _p: Player,
     #[bundle]
     pub ally: AllyBundle,
@@ -114,12 +802,15 @@ pub struct PlayerBundle {
 pub struct AllyBundle {
     pub rigid_body: RigidBody,
     pub velocity: Velocity,
+    pub impulse: ExternalImpulse,
+    pub mass: ReadMassProperties,
     pub ally_type: AllyType,
     pub health: Health,
     pub damage: Damage,
     pub attack_range: AttackRange,
     pub attack_timer: AttackTimer,
-    pub indicator_entity: IndicatorEntity,
+    pub kills: Kills,
+    pub damage_dealt: DamageDealt,
     #[bundle]
     pub sprite: SpriteSheetBundle,
 }
@@ -129,12 +820,13 @@ pub struct EnemyBundle {
     pub speed: Speed,
     pub rigid_body: RigidBody,
     pub velocity: Velocity,
+    pub impulse: ExternalImpulse,
+    pub mass: ReadMassProperties,
     pub enemy_type: EnemyType,
     pub health: Health,
     pub damage: Damage,
     pub attack_range: AttackRange,
     pub attack_timer: AttackTimer,
-    pub indicator_entity: IndicatorEntity,
     #[bundle]
     pub sprite: SpriteSheetBundle,
 }
@@ -143,6 +835,7 @@ pub struct EnemyBundle {
 pub struct ProjectileBundle<C: Component> {
     pub rigid_body: RigidBody,
     pub collider: Collider,
+    pub collision_groups: CollisionGroups,
     pub velocity: Velocity,
     pub damage: Damage,
     pub projectile: Projectile<C>,
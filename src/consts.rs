@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use bevy_rapier2d::prelude::CollisionGroups;
 
 pub const WIDTH: f32 = 1280.0;
 pub const HEIGHT: f32 = 720.0;
@@ -6,8 +7,82 @@ pub const SPRITE_SCALE: f32 = 2.0;
 pub const XEXTENT: (f32, f32) = (-545.0, 545.0);
 pub const YEXTENT: (f32, f32) = (-225.0, 250.0);
 pub const HEALTH_BAR_LEN: f32 = 12.0;
+pub const CORPSE_LIFETIME: f32 = 1.5;
+pub const REVIVE_WINDOW: f32 = 6.0;
+pub const REVIVE_TIME: f32 = 2.0;
+pub const REVIVE_RANGE: f32 = 20.0;
+pub const ALCHEMIST_REVIVE_RANGE: f32 = 80.0;
+pub const REVIVE_HEALTH: f32 = 0.4;
+pub const MELEE_ARC_SLACK: f32 = 10.0;
+pub const UNIT_DAMPING: f32 = 4.0;
+pub const PLAYER_ACCELERATION: f32 = 2000.0;
+pub const PARTY_ACCELERATION: f32 = 1500.0;
+pub const ENEMY_ACCELERATION: f32 = 1000.0;
 pub const PROJECTILE_SPEED: f32 = 750.0;
+pub const CRIT_CHANCE: f64 = 0.1;
+pub const CRIT_MULTIPLIER: f32 = 2.0;
+pub const HIT_FLASH_DURATION: f32 = 0.1;
+pub const DAMAGE_NUMBER_LIFETIME: f32 = 0.6;
+pub const SPLASH_DAMAGE: f32 = 0.5;
+pub const VETERAN_KILLS: u32 = 5;
+pub const ENEMY_WIND_UP: f32 = 0.4;
+pub const ENEMY_SLASH_RADIUS: f32 = 14.0;
+pub const RADIUS_PER_PARTY_MEMBER: f32 = 8.0;
+pub const FORMATION_STIFFNESS: f32 = 5.0;
+pub const PARTY_MAX_SPEED: f32 = 400.0;
+pub const AGGRESSIVE_LEASH: f32 = 2.0;
+pub const DEFENSIVE_SPREAD: f32 = 0.5;
+pub const DASH_SPEED: f32 = 650.0;
+pub const DASH_DURATION: f32 = 0.2;
+pub const DASH_COOLDOWN: f32 = 3.0;
+pub const RALLY_DURATION: f32 = 5.0;
+pub const RALLY_COOLDOWN: f32 = 15.0;
+pub const RALLY_ATTACK_SPEED: f32 = 1.5;
+pub const CAMERA_FOLLOW: f32 = 6.0;
+pub const CAMERA_ZOOM_SPEED: f32 = 2.0;
+pub const CAMERA_MIN_ZOOM: f32 = 0.25;
+pub const CAMERA_MAX_ZOOM: f32 = 0.45;
+pub const CAMERA_MAX_SHAKE: f32 = 6.0;
+pub const TRAUMA_DECAY: f32 = 1.5;
+pub const INDICATOR_MARGIN: f32 = 16.0;
+pub const INDICATOR_CLUSTER_RADIUS: f32 = 32.0;
+pub const INDICATOR_FADE_DISTANCE: f32 = 600.0;
+pub const MINIMAP_WIDTH: f32 = 220.0;
+pub const TOOLTIP_PICK_RADIUS: f32 = 14.0;
 pub const BUTTON_CLICKED: UiColor = UiColor(Color::BLUE);
 pub const BUTTON_HOVERED: UiColor = UiColor(Color::GRAY);
 pub const BUTTON_DEFAULT: UiColor = UiColor(Color::BLACK);
 pub const TRANSPARENT: UiColor = UiColor(Color::rgba(0.0, 0.0, 0.0, 0.0));
+
+pub const ALLY_GROUP: u32 = 1 << 0;
+pub const ENEMY_GROUP: u32 = 1 << 1;
+pub const ALLY_PROJECTILE_GROUP: u32 = 1 << 2;
+pub const ENEMY_PROJECTILE_GROUP: u32 = 1 << 3;
+pub const WALL_GROUP: u32 = 1 << 4;
+pub const ALLY_GROUPS: CollisionGroups = CollisionGroups {
+    memberships: ALLY_GROUP,
+    filters: ALLY_GROUP | ENEMY_GROUP | ENEMY_PROJECTILE_GROUP | WALL_GROUP,
+};
+pub const ENEMY_GROUPS: CollisionGroups = CollisionGroups {
+    memberships: ENEMY_GROUP,
+    filters: ALLY_GROUP | ENEMY_GROUP | ALLY_PROJECTILE_GROUP | WALL_GROUP,
+};
+pub const GHOST_GROUPS: CollisionGroups = CollisionGroups {
+    memberships: ENEMY_GROUP,
+    filters: ALLY_GROUP | ENEMY_GROUP | ALLY_PROJECTILE_GROUP,
+};
+pub const ALLY_PROJECTILE_GROUPS: CollisionGroups = CollisionGroups {
+    memberships: ALLY_PROJECTILE_GROUP,
+    filters: ENEMY_GROUP | WALL_GROUP,
+};
+pub const ENEMY_PROJECTILE_GROUPS: CollisionGroups = CollisionGroups {
+    memberships: ENEMY_PROJECTILE_GROUP,
+    filters: ALLY_GROUP | WALL_GROUP,
+};
+pub const WALL_GROUPS: CollisionGroups = CollisionGroups {
+    memberships: WALL_GROUP,
+    filters: u32::MAX,
+};
+
+pub const RECORDS_FILE: &str = "records.txt";
+pub const MAX_RECORD_ROWS: usize = 12;
@@ -1,16 +1,21 @@
-use std::time::Duration;
+use std::{f32::consts::FRAC_PI_2, time::Duration};
 
 use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::{FillMode, *};
 use bevy_rapier2d::prelude::*;
 use iyes_loopless::prelude::*;
 
 use crate::{
     components::{
-        AllyType, AnimationTimer, EnemyType, HasHealthBar, Health, Indicator, IndicatorEntity,
-        IsDead, MainHealthBar, PartyRadius, Player, Projectile,
+        AllyType, AnimationController, AnimationState, AnimationTimer, Corpse, EnemyType,
+        FocusTarget, HasHealthBar, Health, InParty, Indicator, IsDead, Kills, LastHitBy,
+        MainHealthBar, PartyRadius, Player, Projectile, Revivable, ReviveMarker, WindUp,
     },
-    consts::{BUTTON_CLICKED, BUTTON_DEFAULT, BUTTON_HOVERED, HEALTH_BAR_LEN},
-    resources::Sprites,
+    consts::{
+        BUTTON_CLICKED, BUTTON_DEFAULT, BUTTON_HOVERED, CORPSE_LIFETIME, HEALTH_BAR_LEN,
+        REVIVE_WINDOW,
+    },
+    resources::{RunStats, Sprites},
     GameState,
 };
 
@@ -56,33 +61,71 @@ pub fn go_to_state(state: GameState) -> impl Fn(Commands) {
     }
 }
 
+pub fn back_on_esc(mut commands: Commands, kbd: Res<Input<KeyCode>>) {
+    if kbd.just_pressed(KeyCode::Escape) {
+        commands.insert_resource(NextState(GameState::MainMenu));
+    }
+}
+
+pub fn ready_timer(seconds: f32) -> Timer {
+    let mut timer = Timer::from_seconds(seconds, false);
+    timer.tick(Duration::from_secs_f32(seconds));
+    timer
+}
+
+pub fn steer(current: Vec2, desired: Vec2, max_delta: f32) -> Vec2 {
+    current + (desired - current).clamp_length_max(max_delta)
+}
+
+pub fn cursor_world_position(
+    windows: &Windows,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+) -> Option<Vec2> {
+    let window = windows.get_primary()?;
+    let cursor = window.cursor_position()?;
+    let window_size = Vec2::new(window.width(), window.height());
+    let ndc = (cursor / window_size) * 2.0 - Vec2::ONE;
+    let ndc_to_world = camera_transform.compute_matrix() * camera.projection_matrix().inverse();
+    Some(ndc_to_world.project_point3(ndc.extend(-1.0)).truncate())
+}
+
 pub fn animate_sprites(
     time: Res<Time>,
-    texture_atlases: Res<Assets<TextureAtlas>>,
-    mut query: Query<
-        (
-            &mut AnimationTimer,
-            &mut TextureAtlasSprite,
-            &Handle<TextureAtlas>,
-        ),
-        (
-            Without<Projectile<AllyType>>,
-            Without<Projectile<EnemyType>>,
-            Without<IsDead>,
-        ),
-    >,
+    mut query: Query<(
+        &mut AnimationController,
+        &mut TextureAtlasSprite,
+        &Velocity,
+        Option<&IsDead>,
+    )>,
 ) {
-    for (mut timer, mut sprite, texture_atlas_handle) in &mut query {
-        timer.tick(time.delta());
-        if timer.paused() {
-            sprite.index = 0;
-        } else if timer.just_finished() {
-            let texture_atlas = texture_atlases.get(texture_atlas_handle).unwrap();
-            sprite.index = (sprite.index + 1) % (texture_atlas.textures.len());
-            if sprite.index == 0 && texture_atlas.textures.len() > 1 {
-                sprite.index = 1;
+    for (mut controller, mut sprite, velocity, maybe_dead) in &mut query {
+        let state = if maybe_dead.is_some() {
+            AnimationState::Die
+        } else if controller.is_busy() {
+            controller.state
+        } else if velocity.linvel.length() > 5.0 {
+            AnimationState::Walk
+        } else {
+            AnimationState::Idle
+        };
+        if state != controller.state {
+            controller.play(state);
+            sprite.index = controller.frame;
+        }
+
+        controller.timer.tick(time.delta());
+        if controller.timer.just_finished() && !controller.finished {
+            let clip = controller.clip();
+            if controller.frame < clip.last {
+                controller.frame += 1;
+            } else if clip.looping {
+                controller.frame = clip.first;
+            } else {
+                controller.finished = true;
             }
         }
+        sprite.index = controller.frame;
     }
 }
 
@@ -112,7 +155,7 @@ pub fn animate_attacks(
     }
 }
 
-pub fn regen(mut entities: Query<&mut Health, Without<EnemyType>>) {
+pub fn regen(mut entities: Query<&mut Health, (Without<EnemyType>, Without<IsDead>)>) {
     for mut health in &mut entities {
         health.0 += 0.075;
         if health.0 > health.1 {
@@ -169,65 +212,118 @@ pub fn update_health_bars(
     }
 }
 
-pub fn despawn_zero_health(
+pub fn check_deaths(
     mut commands: Commands,
+    sprites: Res<Sprites>,
     mut player: Query<&mut PartyRadius, With<Player>>,
-    entities: Query<
+    mut killers: Query<&mut Kills>,
+    mut run_stats: ResMut<RunStats>,
+    mut entities: Query<
         (
             Entity,
             &Health,
+            &mut Velocity,
+            &mut Transform,
+            &mut TextureAtlasSprite,
+            &mut Handle<TextureAtlas>,
             Option<&EnemyType>,
-            Option<&IndicatorEntity>,
+            Option<&Player>,
+            Option<&InParty>,
+            Option<&LastHitBy>,
         ),
-        Without<Player>,
+        Without<IsDead>,
     >,
 ) {
-    for (entity, health, maybe_enemy, maybe_indicator) in &entities {
-        if health.0 <= 0.0 {
-            if maybe_enemy.is_some() {
-                let mut radius = player.single_mut();
+    for (
+        entity,
+        health,
+        mut velocity,
+        mut transform,
+        mut sprite,
+        mut texture_atlas,
+        maybe_enemy,
+        maybe_player,
+        maybe_in_party,
+        maybe_last_hit,
+    ) in &mut entities
+    {
+        if health.0 > 0.0 {
+            continue;
+        }
+
+        velocity.linvel = Vec2::ZERO;
+        commands.entity(entity).insert(IsDead).remove::<Collider>();
+
+        if maybe_player.is_some() {
+            *texture_atlas = sprites.player_death.clone();
+            sprite.index = 0;
+            continue;
+        }
+
+        if maybe_enemy.is_some() {
+            if let Ok(mut radius) = player.get_single_mut() {
                 radius.0 += 0.1;
             }
-            if let Some(indicator) = maybe_indicator {
-                if let Some(entity) = indicator.0 {
-                    commands.entity(entity).despawn_recursive();
-                }
+            run_stats.kills += 1;
+            if let Some(mut kills) = maybe_last_hit.and_then(|last| killers.get_mut(last.0).ok()) {
+                kills.0 += 1;
             }
-            commands.entity(entity).despawn_recursive();
+        }
+
+        sprite.index = 0;
+        transform.rotation =
+            Quat::from_rotation_z(if sprite.flip_x { -FRAC_PI_2 } else { FRAC_PI_2 });
+        commands
+            .entity(entity)
+            .remove::<FocusTarget>()
+            .despawn_descendants();
+
+        if maybe_in_party.is_some() {
+            let shape = shapes::Circle {
+                radius: 10.0,
+                ..default()
+            };
+            commands
+                .entity(entity)
+                .insert(Corpse(Timer::from_seconds(REVIVE_WINDOW, false)))
+                .insert(Revivable::default())
+                .with_children(|parent| {
+                    parent
+                        .spawn_bundle(GeometryBuilder::build_as(
+                            &shape,
+                            DrawMode::Outlined {
+                                fill_mode: FillMode::color(Color::NONE),
+                                outline_mode: StrokeMode::new(Color::GREEN, 0.75),
+                            },
+                            Transform::from_translation(Vec3::Z * -0.5),
+                        ))
+                        .insert(ReviveMarker);
+                });
+        } else {
+            commands
+                .entity(entity)
+                .insert(Corpse(Timer::from_seconds(CORPSE_LIFETIME, false)));
         }
     }
 }
-pub fn check_player_death(
+
+pub fn fade_corpses(
     mut commands: Commands,
-    sprites: Res<Sprites>,
-    mut player: Query<
-        (Entity, &Health, &mut Handle<TextureAtlas>),
-        (With<Player>, Without<IsDead>),
-    >,
+    time: Res<Time>,
+    mut corpses: Query<(Entity, &mut Corpse, &mut TextureAtlasSprite)>,
 ) {
-    for (entity, health, mut animation) in &mut player {
-        if health.0 <= 0.0 {
-            *animation = sprites.player_death.clone();
-            commands.entity(entity).insert(IsDead);
+    for (entity, mut corpse, mut sprite) in &mut corpses {
+        corpse.tick(time.delta());
+        sprite.color.set_a(1.0 - corpse.percent());
+        if corpse.finished() {
+            commands.entity(entity).despawn_recursive();
         }
     }
 }
 
 pub fn player_death_animation(
     mut commands: Commands,
-    time: Res<Time>,
-    texture_atlases: Res<Assets<TextureAtlas>>,
-    mut player: Query<
-        (
-            Entity,
-            &mut Health,
-            &mut Velocity,
-            &mut AnimationTimer,
-            &mut TextureAtlasSprite,
-            &Handle<TextureAtlas>,
-        ),
-        (With<Player>, With<IsDead>),
-    >,
+    player: Query<(Entity, &AnimationController), (With<Player>, With<IsDead>)>,
     entities: Query<
         Entity,
         (
@@ -238,39 +334,18 @@ pub fn player_death_animation(
                 With<Indicator>,
                 With<Projectile<AllyType>>,
                 With<Projectile<EnemyType>>,
+                With<WindUp>,
             )>,
         ),
     >,
 ) {
-    for (
-        entity,
-        mut health,
-        mut vel,
-        mut animation_timer,
-        mut texture_atlas_sprite,
-        texture_atlas_handle,
-    ) in &mut player
-    {
-        animation_timer.set_duration(Duration::from_secs_f32(0.05));
-        health.0 = 0.0;
-        vel.linvel = Vec2::ZERO;
-        if animation_timer.paused() {
-            animation_timer.unpause();
-        }
-
-        animation_timer.tick(time.delta());
-        if animation_timer.just_finished() {
-            let texture_atlas = texture_atlases.get(texture_atlas_handle).unwrap();
-            texture_atlas_sprite.index =
-                (texture_atlas_sprite.index + 1) % texture_atlas.textures.len();
-
-            if texture_atlas_sprite.index == 0 {
+    for (entity, controller) in &player {
+        if controller.finished {
+            commands.entity(entity).despawn_recursive();
+            for entity in &entities {
                 commands.entity(entity).despawn_recursive();
-                for entity in &entities {
-                    commands.entity(entity).despawn_recursive();
-                }
-                commands.insert_resource(NextState(GameState::MainMenu));
             }
+            commands.insert_resource(NextState(GameState::MainMenu));
         }
     }
 }
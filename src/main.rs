@@ -1,3 +1,5 @@
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
 use bevy::{prelude::*, render::texture::ImageSettings};
 use bevy_asset_loader::prelude::*;
 use bevy_prototype_lyon::prelude::*;
@@ -24,7 +26,9 @@ pub enum GameState {
     Load,
     Setup,
     MainMenu,
+    ClassSelect,
     Info,
+    Records,
     InGame,
 }
 
@@ -54,15 +58,30 @@ fn main() {
         .add_plugin(ShapePlugin)
         .add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
         .add_plugin(MainMenuPlugin)
+        .add_plugin(BestiaryPlugin)
+        .add_plugin(RecordsPlugin)
         .add_plugin(SpawnPlugin)
         .add_plugin(PlayerPlugin)
+        .add_plugin(AbilitiesPlugin)
+        .add_plugin(PartyPlugin)
+        .add_plugin(TargetingPlugin)
+        .add_plugin(RevivePlugin)
         .add_plugin(AutoBattlePlugin)
+        .add_plugin(FeedbackPlugin)
+        .add_plugin(DebugPlugin)
+        .add_plugin(CameraPlugin)
+        .add_plugin(IndicatorsPlugin)
+        .add_plugin(MinimapPlugin)
+        .add_plugin(RosterPlugin)
+        .add_plugin(TooltipPlugin)
+        .add_plugin(HudPlugin)
         .add_enter_system(GameState::Setup, setup)
         .add_system(animate_sprites)
         .add_system(animate_attacks)
         .add_system(spawn_health_bars)
         .add_system(update_health_bars)
-        .add_system(despawn_zero_health)
+        .add_system(check_deaths)
+        .add_system(fade_corpses)
         .add_system(regen)
         .run();
 }
@@ -85,7 +104,9 @@ fn setup(mut commands: Commands, sprites: Res<Sprites>) {
             )),
         ))
         .insert(RigidBody::Fixed)
-        .insert(Collider::cuboid(10.0, 0.5 * (YEXTENT.1 - YEXTENT.0)));
+        .insert(Collider::cuboid(10.0, 0.5 * (YEXTENT.1 - YEXTENT.0)))
+        .insert(WALL_GROUPS)
+        .insert(Wall);
     commands
         .spawn_bundle(TransformBundle::from_transform(
             Transform::from_translation(Vec3::new(
@@ -95,7 +116,9 @@ fn setup(mut commands: Commands, sprites: Res<Sprites>) {
             )),
         ))
         .insert(RigidBody::Fixed)
-        .insert(Collider::cuboid(10.0, 0.5 * (YEXTENT.1 - YEXTENT.0)));
+        .insert(Collider::cuboid(10.0, 0.5 * (YEXTENT.1 - YEXTENT.0)))
+        .insert(WALL_GROUPS)
+        .insert(Wall);
     commands
         .spawn_bundle(TransformBundle::from_transform(
             Transform::from_translation(Vec3::new(
@@ -105,7 +128,9 @@ fn setup(mut commands: Commands, sprites: Res<Sprites>) {
             )),
         ))
         .insert(RigidBody::Fixed)
-        .insert(Collider::cuboid(0.5 * (XEXTENT.1 - XEXTENT.0), 10.0));
+        .insert(Collider::cuboid(0.5 * (XEXTENT.1 - XEXTENT.0), 10.0))
+        .insert(WALL_GROUPS)
+        .insert(Wall);
     commands
         .spawn_bundle(TransformBundle::from_transform(
             Transform::from_translation(Vec3::new(
@@ -115,7 +140,9 @@ fn setup(mut commands: Commands, sprites: Res<Sprites>) {
             )),
         ))
         .insert(RigidBody::Fixed)
-        .insert(Collider::cuboid(0.5 * (XEXTENT.1 - XEXTENT.0), 10.0));
+        .insert(Collider::cuboid(0.5 * (XEXTENT.1 - XEXTENT.0), 10.0))
+        .insert(WALL_GROUPS)
+        .insert(Wall);
 
     commands.insert_resource(NextState(GameState::MainMenu));
 }
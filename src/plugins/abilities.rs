@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use iyes_loopless::prelude::*;
+
+use crate::{
+    components::{
+        DashCooldown, Dashing, Heading, InParty, Invulnerable, IsDead, Player, Rallied,
+        RallyCooldown,
+    },
+    consts::{DASH_DURATION, DASH_SPEED, RALLY_DURATION},
+    GameState,
+};
+
+pub struct AbilitiesPlugin;
+
+impl Plugin for AbilitiesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::InGame)
+                .label("first")
+                .with_system(tick_cooldowns)
+                .with_system(dash)
+                .with_system(rally)
+                .with_system(update_dash)
+                .with_system(end_rally)
+                .into(),
+        );
+    }
+}
+
+fn tick_cooldowns(
+    time: Res<Time>,
+    mut player: Query<(&mut DashCooldown, &mut RallyCooldown), With<Player>>,
+) {
+    for (mut dash_cooldown, mut rally_cooldown) in &mut player {
+        dash_cooldown.tick(time.delta());
+        rally_cooldown.tick(time.delta());
+    }
+}
+
+fn dash(
+    mut commands: Commands,
+    keyboard: Res<Input<KeyCode>>,
+    mut player: Query<
+        (
+            Entity,
+            &Heading,
+            &mut Velocity,
+            &mut DashCooldown,
+            &mut TextureAtlasSprite,
+        ),
+        (With<Player>, Without<IsDead>),
+    >,
+) {
+    if !keyboard.just_pressed(KeyCode::Space) {
+        return;
+    }
+    if let Ok((entity, heading, mut velocity, mut cooldown, mut sprite)) = player.get_single_mut() {
+        if cooldown.finished() {
+            cooldown.reset();
+            velocity.linvel = heading.0 * DASH_SPEED;
+            sprite.color.set_a(0.5);
+            commands
+                .entity(entity)
+                .insert(Dashing(Timer::from_seconds(DASH_DURATION, false)))
+                .insert(Invulnerable);
+        }
+    }
+}
+
+fn update_dash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut dashing: Query<
+        (
+            Entity,
+            &Heading,
+            &mut Dashing,
+            &mut Velocity,
+            &mut TextureAtlasSprite,
+        ),
+        Without<IsDead>,
+    >,
+) {
+    for (entity, heading, mut dashing, mut velocity, mut sprite) in &mut dashing {
+        velocity.linvel = heading.0 * DASH_SPEED;
+        dashing.tick(time.delta());
+        if dashing.finished() {
+            sprite.color.set_a(1.0);
+            commands
+                .entity(entity)
+                .remove::<Dashing>()
+                .remove::<Invulnerable>();
+        }
+    }
+}
+
+fn rally(
+    mut commands: Commands,
+    keyboard: Res<Input<KeyCode>>,
+    mut player: Query<(Entity, &mut RallyCooldown), (With<Player>, Without<IsDead>)>,
+    party_members: Query<Entity, (With<InParty>, Without<IsDead>)>,
+) {
+    if !keyboard.just_pressed(KeyCode::Q) {
+        return;
+    }
+    if let Ok((player_entity, mut cooldown)) = player.get_single_mut() {
+        if cooldown.finished() {
+            cooldown.reset();
+            for entity in std::iter::once(player_entity).chain(&party_members) {
+                commands
+                    .entity(entity)
+                    .insert(Rallied(Timer::from_seconds(RALLY_DURATION, false)));
+            }
+        }
+    }
+}
+
+fn end_rally(mut commands: Commands, time: Res<Time>, mut rallied: Query<(Entity, &mut Rallied)>) {
+    for (entity, mut rallied) in &mut rallied {
+        rallied.tick(time.delta());
+        if rallied.finished() {
+            commands.entity(entity).remove::<Rallied>();
+        }
+    }
+}
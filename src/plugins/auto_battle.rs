@@ -1,16 +1,25 @@
 use std::marker::PhantomData;
 
 use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::{FillMode, *};
 use bevy_rapier2d::prelude::*;
 use iyes_loopless::prelude::*;
+use rand::prelude::*;
 
 use crate::{
     components::{
-        AllyType, AnimationTimer, AttackRange, AttackTimer, Damage, EnemyType, Health, Projectile,
-        ProjectileBundle, Sound,
+        AllyType, AnimationController, AnimationState, AnimationTimer, AttackRange, AttackTimer,
+        Chaining, Damage, DamageDealt, DamageKind, EnemyType, Explosion, FocusTarget, Health,
+        Homing, InParty, Invulnerable, IsDead, Kills, Knockback, LastHitBy, Owner, Piercing,
+        Player, PlayerClass, Projectile, ProjectileBundle, ProjectileType, Rallied, Sound, Splash,
+        Wall, WindUp,
     },
-    consts::PROJECTILE_SPEED,
-    resources::{Sounds, Sprites},
+    consts::{
+        ALLY_PROJECTILE_GROUPS, CRIT_CHANCE, CRIT_MULTIPLIER, ENEMY_PROJECTILE_GROUPS,
+        ENEMY_SLASH_RADIUS, ENEMY_WIND_UP, PROJECTILE_SPEED, RALLY_ATTACK_SPEED, SPLASH_DAMAGE,
+    },
+    plugins::HitEvent,
+    resources::{CombatRng, Sounds, Sprites},
     GameState,
 };
 
@@ -20,7 +29,8 @@ pub struct AutoBattlePlugin;
 
 impl Plugin for AutoBattlePlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<AttackEvent<AllyType>>()
+        app.insert_resource(CombatRng(StdRng::from_entropy()))
+            .add_event::<AttackEvent<AllyType>>()
             .add_event::<AttackEvent<EnemyType>>()
             .add_system_set(
                 ConditionSet::new()
@@ -31,6 +41,10 @@ impl Plugin for AutoBattlePlugin {
                     .with_system(collide_projectiles::<EnemyType, AllyType>)
                     .with_system(handle_ally_attacks)
                     .with_system(handle_enemy_attacks)
+                    .with_system(resolve_wind_ups)
+                    .with_system(despawn_projectiles_on_walls)
+                    .with_system(steer_homing_projectiles)
+                    .with_system(fade_explosions)
                     .into(),
             );
     }
@@ -39,15 +53,32 @@ impl Plugin for AutoBattlePlugin {
 fn auto_battle<A, T>(
     time: Res<Time>,
     mut attack_events: EventWriter<AttackEvent<A>>,
-    mut attackers: Query<(Entity, &Transform, &AttackRange, &mut AttackTimer, &A), Without<T>>,
-    targets: Query<(Entity, &Transform), (With<T>, Without<A>)>,
+    mut attackers: Query<
+        (
+            Entity,
+            &Transform,
+            &AttackRange,
+            &mut AttackTimer,
+            &A,
+            Option<&Rallied>,
+            Option<&mut AnimationController>,
+        ),
+        (Without<T>, Without<IsDead>),
+    >,
+    targets: Query<
+        (Entity, &Transform, Option<&FocusTarget>),
+        (With<T>, Without<A>, Without<IsDead>),
+    >,
 ) where
     A: Component + Clone,
     T: Component,
 {
-    for (attacker_entity, attacker_transform, range, mut timer, ty) in &mut attackers {
+    for (attacker_entity, attacker_transform, range, mut timer, ty, rallied, controller) in
+        &mut attackers
+    {
         let mut closest = (f32::MAX, Entity::from_raw(0), Entity::from_raw(0));
-        for (target_entity, target_transform) in &targets {
+        let mut focused = None;
+        for (target_entity, target_transform, maybe_focus) in &targets {
             let dist = attacker_transform
                 .translation
                 .truncate()
@@ -55,12 +86,27 @@ fn auto_battle<A, T>(
             if dist < closest.0 {
                 closest = (dist, attacker_entity, target_entity);
             }
+            if maybe_focus.is_some() {
+                focused = Some((dist, attacker_entity, target_entity));
+            }
+        }
+        if let Some(focused) = focused {
+            if focused.0 <= range.0 {
+                closest = focused;
+            }
         }
 
-        timer.tick(time.delta());
+        if rallied.is_some() {
+            timer.tick(time.delta().mul_f32(RALLY_ATTACK_SPEED));
+        } else {
+            timer.tick(time.delta());
+        }
 
         if timer.just_finished() && closest.0 <= range.0 {
             attack_events.send(AttackEvent(ty.clone(), closest.1, closest.2));
+            if let Some(mut controller) = controller {
+                controller.play(AnimationState::Attack);
+            }
         }
     }
 }
@@ -68,68 +114,166 @@ fn auto_battle<A, T>(
 fn collide_projectiles<A, T>(
     mut commands: Commands,
     audio: Res<Audio>,
+    mut hit_events: EventWriter<HitEvent>,
     mut projectiles: Query<(
+        &Transform,
         &Damage,
         &mut Projectile<A>,
         Option<&AnimationTimer>,
         &Sound,
         Option<&mut Velocity>,
+        Option<&DamageKind>,
+        Option<&Knockback>,
+        Option<&Owner>,
+        (
+            Option<&mut Piercing>,
+            Option<&Splash>,
+            Option<&mut Chaining>,
+        ),
     )>,
-    mut targets: Query<&mut Health, With<T>>,
+    mut targets: Query<
+        (
+            Entity,
+            &mut Health,
+            &Transform,
+            &mut ExternalImpulse,
+            &ReadMassProperties,
+            Option<&mut AnimationController>,
+        ),
+        (
+            With<T>,
+            Without<Projectile<A>>,
+            Without<Invulnerable>,
+            Without<IsDead>,
+        ),
+    >,
+    mut dealers: Query<&mut DamageDealt>,
+    attackers: Query<&EnemyType>,
+    positions: Query<&Transform, Without<Projectile<A>>>,
     mut collision_events: EventReader<CollisionEvent>,
+    mut combat_rng: ResMut<CombatRng>,
 ) where
     A: Component,
     T: Component,
 {
-    let mut already_processed = Vec::new();
+    let rng = &mut combat_rng.0;
     for event in collision_events.iter() {
         if let CollisionEvent::Started(e1, e2, _) = event {
-            if !already_processed.contains(e1) && !already_processed.contains(e2) {
-                if let Ok((damage, mut projectile, animation_timer, sound, vel)) =
-                    projectiles.get_mut(*e1)
-                {
-                    if projectile.0 {
-                        if let Ok(mut health) = targets.get_mut(*e2) {
-                            already_processed.push(*e1);
-                            projectile.0 = false;
-                            health.0 -= damage.0;
-                            if let Some(mut vel) = vel {
-                                vel.linvel = Vec2::ZERO;
-                            }
-                            audio.play_with_settings(
-                                sound.0.clone(),
-                                PlaybackSettings::ONCE.with_volume(0.3),
-                            );
-                            if animation_timer.is_none() {
-                                commands
-                                    .entity(*e1)
-                                    .insert(AnimationTimer(Timer::from_seconds(0.1, true)));
-                            }
-                        }
+            for (projectile_entity, target_entity) in [(*e1, *e2), (*e2, *e1)] {
+                let (
+                    projectile_transform,
+                    damage,
+                    mut projectile,
+                    animation_timer,
+                    sound,
+                    mut vel,
+                    kind,
+                    knockback,
+                    owner,
+                    (piercing, splash, chaining),
+                ) = match projectiles.get_mut(projectile_entity) {
+                    Ok(projectile) => projectile,
+                    Err(_) => continue,
+                };
+                if !projectile.0 || !targets.contains(target_entity) {
+                    continue;
+                }
+                let impact = projectile_transform.translation.truncate();
+
+                let mut hits = vec![(target_entity, damage.0)];
+                if let Some(splash) = splash {
+                    hits.extend(
+                        targets
+                            .iter()
+                            .filter(|(entity, _, transform, ..)| {
+                                *entity != target_entity
+                                    && transform.translation.truncate().distance(impact)
+                                        <= splash.radius
+                            })
+                            .map(|(entity, ..)| (entity, damage.0 * SPLASH_DAMAGE)),
+                    );
+                    let shape = shapes::Circle {
+                        radius: splash.radius,
+                        ..default()
+                    };
+                    commands
+                        .spawn_bundle(GeometryBuilder::build_as(
+                            &shape,
+                            DrawMode::Fill(FillMode::color(Color::rgba(1.0, 0.5, 0.0, 0.5))),
+                            Transform::from_translation(impact.extend(4.0)),
+                        ))
+                        .insert(Explosion(Timer::from_seconds(0.3, false)));
+                }
+
+                for (entity, amount) in hits {
+                    let (_, mut health, transform, mut impulse, mass, controller) =
+                        targets.get_mut(entity).unwrap();
+                    let crit = rng.gen_bool(CRIT_CHANCE);
+                    let amount = if crit {
+                        amount * CRIT_MULTIPLIER
+                    } else {
+                        amount
+                    };
+                    health.0 -= amount;
+                    hit_events.send(HitEvent {
+                        target: entity,
+                        position: transform.translation.truncate(),
+                        amount,
+                        kind: kind.copied().unwrap_or_default(),
+                        crit,
+                    });
+
+                    if let Some(knockback) = knockback {
+                        let origin = owner
+                            .and_then(|owner| positions.get(owner.0).ok())
+                            .map_or(impact, |owner| owner.translation.truncate());
+                        let dir = (transform.translation.truncate() - origin).normalize_or_zero();
+                        // Rapier impulses are mass * velocity, so scale by the body's mass
+                        // to give every target the same push.
+                        impulse.impulse = dir * knockback.0 * mass.0.mass;
                     }
-                } else if let Ok((damage, mut projectile, animation_timer, sound, vel)) =
-                    projectiles.get_mut(*e2)
-                {
-                    if projectile.0 {
-                        if let Ok(mut health) = targets.get_mut(*e1) {
-                            already_processed.push(*e2);
-                            projectile.0 = false;
-                            health.0 -= damage.0;
-                            if let Some(mut vel) = vel {
-                                vel.linvel = Vec2::ZERO;
-                            }
-                            audio.play_with_settings(
-                                sound.0.clone(),
-                                PlaybackSettings::ONCE.with_volume(0.3),
-                            );
-                            if animation_timer.is_none() {
-                                commands
-                                    .entity(*e2)
-                                    .insert(AnimationTimer(Timer::from_seconds(0.1, true)));
-                            }
+                    if let Some(mut controller) = controller {
+                        controller.play(AnimationState::Hurt);
+                    }
+                    if let Some(owner) = owner {
+                        commands
+                            .entity(entity)
+                            .insert(LastHitBy(owner.0, attackers.get(owner.0).ok().copied()));
+                        if let Ok(mut dealt) = dealers.get_mut(owner.0) {
+                            dealt.0 += amount;
                         }
                     }
                 }
+                audio.play_with_settings(sound.0.clone(), PlaybackSettings::ONCE.with_volume(0.3));
+
+                if let Some(mut chaining) = chaining.filter(|chaining| chaining.remaining > 0) {
+                    chaining.hit.push(target_entity);
+                    let next = targets
+                        .iter()
+                        .filter(|(entity, ..)| !chaining.hit.contains(entity))
+                        .map(|(_, _, transform, ..)| transform.translation.truncate())
+                        .filter(|pos| pos.distance(impact) <= chaining.range)
+                        .min_by(|a, b| a.distance(impact).total_cmp(&b.distance(impact)));
+                    if let (Some(next), Some(vel)) = (next, vel.as_mut()) {
+                        chaining.remaining -= 1;
+                        vel.linvel = (next - impact).normalize_or_zero() * vel.linvel.length();
+                        continue;
+                    }
+                } else if let Some(mut piercing) = piercing.filter(|piercing| piercing.0 > 0) {
+                    piercing.0 -= 1;
+                    continue;
+                }
+
+                if let Some(mut vel) = vel {
+                    vel.linvel = Vec2::ZERO;
+                    commands.entity(projectile_entity).remove::<Homing>();
+                }
+                projectile.0 = false;
+                if animation_timer.is_none() {
+                    commands
+                        .entity(projectile_entity)
+                        .insert(AnimationTimer(Timer::from_seconds(0.1, true)));
+                }
             }
         }
     }
@@ -140,14 +284,24 @@ fn handle_ally_attacks(
     sprites: Res<Sprites>,
     sounds: Res<Sounds>,
     mut attack_events: EventReader<AttackEvent<AllyType>>,
-    allies: Query<(&Transform, &Damage, &AllyType)>,
+    allies: Query<(
+        &Transform,
+        &Damage,
+        &AttackRange,
+        &AllyType,
+        &Kills,
+        Option<&PlayerClass>,
+    )>,
     enemies: Query<&Transform, With<EnemyType>>,
+    mut healable: Query<&mut Health, (Or<(With<InParty>, With<Player>)>, Without<IsDead>)>,
 ) {
     for AttackEvent(_, ally_entity, enemy_entity) in attack_events.iter() {
-        if let Ok((ally_transform, damage, ally_type)) = allies.get(*ally_entity) {
+        if let Ok((ally_transform, damage, range, ally_type, kills, player_class)) =
+            allies.get(*ally_entity)
+        {
             if let Ok(enemy_transform) = enemies.get(*enemy_entity) {
-                match ally_type {
-                    AllyType::Archer => {
+                match (ally_type, player_class) {
+                    (AllyType::Archer, _) | (AllyType::Player, Some(PlayerClass::Archer)) => {
                         let dir = (enemy_transform.translation.truncate()
                             - ally_transform.translation.truncate())
                         .normalize();
@@ -160,6 +314,7 @@ fn handle_ally_attacks(
                                 },
                                 damage: Damage(damage.0),
                                 projectile: Projectile::<AllyType>(true, PhantomData),
+                                collision_groups: ALLY_PROJECTILE_GROUPS,
                                 sprite: SpriteSheetBundle {
                                     texture_atlas: sprites.arrow.clone(),
                                     transform: Transform::from_translation(
@@ -171,44 +326,131 @@ fn handle_ally_attacks(
                                     .with_scale(Vec3::splat(1.5)),
                                     ..default()
                                 },
-                                collider: Collider::cuboid(4.0, 8.0),
+                                collider: ProjectileType::Arrow.collider(),
                                 ..default()
                             })
                             .insert(Sensor)
                             .insert(ActiveEvents::COLLISION_EVENTS)
+                            .insert(Owner(*ally_entity))
+                            .insert(Piercing(match player_class {
+                                Some(class) => class.piercing(),
+                                None => ally_type.piercing(kills.0),
+                            }))
                             .insert(Sound(sounds.arrow.clone()));
                     }
-                    AllyType::Wizard => {
-                        commands
-                            .spawn_bundle(ProjectileBundle {
-                                velocity: Velocity {
-                                    linvel: (enemy_transform.translation.truncate()
-                                        - ally_transform.translation.truncate())
-                                    .normalize()
-                                        * PROJECTILE_SPEED,
-                                    ..default()
-                                },
-                                damage: Damage(damage.0),
-                                projectile: Projectile::<AllyType>(true, PhantomData),
-                                sprite: SpriteSheetBundle {
-                                    texture_atlas: sprites.fireball.clone(),
-                                    transform: Transform::from_translation(
-                                        ally_transform.translation,
-                                    )
+                    (AllyType::Wizard, _) => {
+                        let mut fireball = commands.spawn_bundle(ProjectileBundle {
+                            velocity: Velocity {
+                                linvel: (enemy_transform.translation.truncate()
+                                    - ally_transform.translation.truncate())
+                                .normalize()
+                                    * PROJECTILE_SPEED,
+                                ..default()
+                            },
+                            damage: Damage(damage.0),
+                            projectile: Projectile::<AllyType>(true, PhantomData),
+                            collision_groups: ALLY_PROJECTILE_GROUPS,
+                            sprite: SpriteSheetBundle {
+                                texture_atlas: sprites.fireball.clone(),
+                                transform: Transform::from_translation(ally_transform.translation)
                                     .with_scale(Vec3::splat(2.5)),
+                                ..default()
+                            },
+                            collider: ProjectileType::Fireball.collider(),
+                            ..default()
+                        });
+                        fireball
+                            .insert(Sensor)
+                            .insert(ActiveEvents::COLLISION_EVENTS)
+                            .insert(DamageKind::Fire)
+                            .insert(Owner(*ally_entity))
+                            .insert(Knockback(ally_type.knockback()))
+                            .insert(Sound(sounds.fireball.clone()));
+                        if let Some(splash) = ally_type.splash() {
+                            fireball.insert(splash);
+                        }
+                    }
+                    (AllyType::Player, Some(class @ PlayerClass::Alchemist)) => {
+                        let mut flask = commands.spawn_bundle(ProjectileBundle {
+                            velocity: Velocity {
+                                linvel: (enemy_transform.translation.truncate()
+                                    - ally_transform.translation.truncate())
+                                .normalize()
+                                    * PROJECTILE_SPEED
+                                    * 0.5,
+                                ..default()
+                            },
+                            damage: Damage(damage.0),
+                            projectile: Projectile::<AllyType>(true, PhantomData),
+                            collision_groups: ALLY_PROJECTILE_GROUPS,
+                            sprite: SpriteSheetBundle {
+                                texture_atlas: sprites.fireball.clone(),
+                                sprite: TextureAtlasSprite {
+                                    color: Color::LIME_GREEN,
                                     ..default()
                                 },
-                                collider: Collider::cuboid(4.0, 4.0),
+                                transform: Transform::from_translation(ally_transform.translation)
+                                    .with_scale(Vec3::splat(1.5)),
                                 ..default()
-                            })
+                            },
+                            collider: ProjectileType::Flask.collider(),
+                            ..default()
+                        });
+                        flask
                             .insert(Sensor)
                             .insert(ActiveEvents::COLLISION_EVENTS)
+                            .insert(DamageKind::Poison)
+                            .insert(Owner(*ally_entity))
                             .insert(Sound(sounds.fireball.clone()));
+                        if let Some(chaining) = class.chaining() {
+                            flask.insert(chaining);
+                        }
+
+                        if let Some(heal) = class.heal() {
+                            if let Some(mut health) = healable
+                                .iter_mut()
+                                .filter(|health| health.0 < health.1)
+                                .min_by(|a, b| (a.0 / a.1).total_cmp(&(b.0 / b.1)))
+                            {
+                                health.0 = (health.0 + heal).min(health.1);
+                            }
+                        }
                     }
                     _ => {
                         let dir = (enemy_transform.translation.truncate()
                             - ally_transform.translation.truncate())
                         .normalize();
+                        let melee_arc = match player_class {
+                            Some(class) => class.melee_arc(range.0),
+                            None => ally_type.melee_arc(range.0),
+                        };
+                        if let Some(melee_arc) = melee_arc {
+                            commands
+                                .spawn_bundle(SpriteSheetBundle {
+                                    texture_atlas: sprites.slash.clone(),
+                                    transform: Transform::from_translation(
+                                        ally_transform.translation
+                                            + (dir * melee_arc.reach / 2.0).extend(1.0),
+                                    )
+                                    .with_scale(Vec3::splat(melee_arc.scale()))
+                                    .with_rotation(
+                                        Quat::from_rotation_z(Vec2::NEG_Y.angle_between(dir)),
+                                    ),
+                                    ..default()
+                                })
+                                .insert(AnimationTimer(Timer::from_seconds(0.03, true)))
+                                .insert(melee_arc.collider())
+                                .insert(ALLY_PROJECTILE_GROUPS)
+                                .insert(Sensor)
+                                .insert(ActiveEvents::COLLISION_EVENTS)
+                                .insert(Projectile::<AllyType>(true, PhantomData))
+                                .insert(Piercing(u32::MAX))
+                                .insert(Damage(damage.0))
+                                .insert(Knockback(ally_type.knockback()))
+                                .insert(Owner(*ally_entity))
+                                .insert(Sound(sounds.slash.clone()));
+                            continue;
+                        }
                         commands
                             .spawn_bundle(SpriteSheetBundle {
                                 texture_atlas: sprites.slash.clone(),
@@ -222,11 +464,14 @@ fn handle_ally_attacks(
                                 ..default()
                             })
                             .insert(AnimationTimer(Timer::from_seconds(0.03, true)))
-                            .insert(Collider::cuboid(5., 2.))
+                            .insert(ProjectileType::Slash.collider())
+                            .insert(ALLY_PROJECTILE_GROUPS)
                             .insert(Sensor)
                             .insert(ActiveEvents::COLLISION_EVENTS)
                             .insert(Projectile::<AllyType>(true, PhantomData))
                             .insert(Damage(damage.0))
+                            .insert(Knockback(ally_type.knockback()))
+                            .insert(Owner(*ally_entity))
                             .insert(Sound(sounds.slash.clone()));
                     }
                 }
@@ -246,52 +491,174 @@ fn handle_enemy_attacks(
     for AttackEvent(_, enemy_entity, ally_entity) in attack_events.iter() {
         if let Ok((enemy_transform, damage, enemy_type)) = enemies.get(*enemy_entity) {
             if let Ok(ally_transform) = allies.get(*ally_entity) {
-                match enemy_type {
-                    EnemyType::EvilWizard => {
-                        commands
-                            .spawn_bundle(ProjectileBundle {
-                                velocity: Velocity {
-                                    linvel: (ally_transform.translation.truncate()
-                                        - enemy_transform.translation.truncate())
-                                    .normalize()
-                                        * PROJECTILE_SPEED,
-                                    ..default()
-                                },
-                                damage: Damage(damage.0),
-                                projectile: Projectile::<EnemyType>(true, PhantomData),
-                                sprite: SpriteSheetBundle {
-                                    texture_atlas: sprites.fireball.clone(),
-                                    transform: Transform::from_translation(
-                                        enemy_transform.translation,
-                                    )
-                                    .with_scale(Vec3::splat(2.5)),
-                                    ..default()
-                                },
-                                collider: Collider::cuboid(4.0, 4.0),
-                                ..default()
-                            })
-                            .insert(Sensor)
-                            .insert(ActiveEvents::COLLISION_EVENTS)
-                            .insert(Sound(sounds.fireball.clone()));
-                    }
-                    _ => {
-                        commands
-                            .spawn_bundle(SpriteSheetBundle {
-                                texture_atlas: sprites.slash.clone(),
-                                transform: *ally_transform,
-                                visibility: Visibility { is_visible: false },
-                                ..default()
-                            })
-                            .insert(AnimationTimer(Timer::from_seconds(0.001, true)))
-                            .insert(Collider::cuboid(5., 2.))
-                            .insert(Sensor)
-                            .insert(ActiveEvents::COLLISION_EVENTS)
-                            .insert(Projectile::<EnemyType>(true, PhantomData))
-                            .insert(Damage(damage.0))
-                            .insert(Sound(sounds.enemy_attack.clone()));
+                if enemy_type.is_ranged() {
+                    let mut fireball = commands.spawn_bundle(ProjectileBundle {
+                        velocity: Velocity {
+                            linvel: (ally_transform.translation.truncate()
+                                - enemy_transform.translation.truncate())
+                            .normalize()
+                                * PROJECTILE_SPEED,
+                            ..default()
+                        },
+                        damage: Damage(damage.0),
+                        projectile: Projectile::<EnemyType>(true, PhantomData),
+                        collision_groups: ENEMY_PROJECTILE_GROUPS,
+                        sprite: SpriteSheetBundle {
+                            texture_atlas: sprites.fireball.clone(),
+                            transform: Transform::from_translation(enemy_transform.translation)
+                                .with_scale(Vec3::splat(2.5)),
+                            ..default()
+                        },
+                        collider: ProjectileType::Fireball.collider(),
+                        ..default()
+                    });
+                    fireball
+                        .insert(Sensor)
+                        .insert(ActiveEvents::COLLISION_EVENTS)
+                        .insert(DamageKind::Fire)
+                        .insert(Owner(*enemy_entity))
+                        .insert(Knockback(enemy_type.knockback()))
+                        .insert(Sound(sounds.fireball.clone()));
+                    if let Some(turn_rate) = enemy_type.homing_turn_rate() {
+                        fireball.insert(Homing {
+                            target: *ally_entity,
+                            turn_rate,
+                        });
                     }
+                } else {
+                    let shape = shapes::Circle {
+                        radius: ENEMY_SLASH_RADIUS,
+                        ..default()
+                    };
+                    commands
+                        .spawn_bundle(GeometryBuilder::build_as(
+                            &shape,
+                            DrawMode::Fill(FillMode::color(Color::rgba(1.0, 0.0, 0.0, 0.25))),
+                            Transform::from_translation(
+                                ally_transform.translation.truncate().extend(0.5),
+                            )
+                            .with_scale(Vec3::ZERO),
+                        ))
+                        .insert(Owner(*enemy_entity))
+                        .insert(WindUp {
+                            timer: Timer::from_seconds(ENEMY_WIND_UP, false),
+                            damage: Damage(damage.0),
+                            knockback: Knockback(enemy_type.knockback()),
+                        });
                 }
             }
         }
     }
 }
+
+fn resolve_wind_ups(
+    mut commands: Commands,
+    time: Res<Time>,
+    sprites: Res<Sprites>,
+    sounds: Res<Sounds>,
+    mut wind_ups: Query<(Entity, &mut WindUp, &mut Transform, &Owner)>,
+    attackers: Query<(), (With<EnemyType>, Without<IsDead>)>,
+) {
+    for (entity, mut wind_up, mut transform, owner) in &mut wind_ups {
+        if !attackers.contains(owner.0) {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        wind_up.timer.tick(time.delta());
+        transform.scale = Vec3::splat(wind_up.timer.percent());
+
+        if wind_up.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            commands
+                .spawn_bundle(SpriteSheetBundle {
+                    texture_atlas: sprites.slash.clone(),
+                    sprite: TextureAtlasSprite {
+                        color: Color::rgb(1.0, 0.4, 0.4),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(
+                        transform.translation.truncate().extend(3.0),
+                    )
+                    .with_scale(Vec3::splat(2.5)),
+                    ..default()
+                })
+                .insert(AnimationTimer(Timer::from_seconds(0.03, true)))
+                .insert(ProjectileType::EnemySlash.collider())
+                .insert(ENEMY_PROJECTILE_GROUPS)
+                .insert(Sensor)
+                .insert(ActiveEvents::COLLISION_EVENTS)
+                .insert(Projectile::<EnemyType>(true, PhantomData))
+                .insert(wind_up.damage)
+                .insert(wind_up.knockback)
+                .insert(Owner(owner.0))
+                .insert(Sound(sounds.enemy_attack.clone()));
+        }
+    }
+}
+
+fn despawn_projectiles_on_walls(
+    mut commands: Commands,
+    walls: Query<(), With<Wall>>,
+    projectiles: Query<
+        (),
+        (
+            With<Velocity>,
+            Or<(With<Projectile<AllyType>>, With<Projectile<EnemyType>>)>,
+        ),
+    >,
+    mut collision_events: EventReader<CollisionEvent>,
+) {
+    for event in collision_events.iter() {
+        if let CollisionEvent::Started(e1, e2, _) = event {
+            for (wall, projectile) in [(*e1, *e2), (*e2, *e1)] {
+                if walls.contains(wall) && projectiles.contains(projectile) {
+                    commands.entity(projectile).despawn_recursive();
+                }
+            }
+        }
+    }
+}
+
+fn steer_homing_projectiles(
+    time: Res<Time>,
+    mut projectiles: Query<(&Homing, &Transform, &mut Velocity)>,
+    targets: Query<&Transform, Without<IsDead>>,
+) {
+    for (homing, transform, mut velocity) in &mut projectiles {
+        if let Ok(target) = targets.get(homing.target) {
+            let desired = target.translation.truncate() - transform.translation.truncate();
+            if velocity.linvel == Vec2::ZERO
+                || desired == Vec2::ZERO
+                || !velocity.linvel.is_finite()
+                || !desired.is_finite()
+            {
+                continue;
+            }
+            let max_turn = homing.turn_rate * time.delta_seconds();
+            let turn = velocity
+                .linvel
+                .angle_between(desired)
+                .clamp(-max_turn, max_turn);
+            velocity.linvel = Vec2::from_angle(turn).rotate(velocity.linvel);
+        }
+    }
+}
+
+fn fade_explosions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut explosions: Query<(Entity, &mut Explosion, &mut DrawMode)>,
+) {
+    for (entity, mut explosion, mut draw_mode) in &mut explosions {
+        explosion.tick(time.delta());
+        *draw_mode = DrawMode::Fill(FillMode::color(Color::rgba(
+            1.0,
+            0.5,
+            0.0,
+            0.5 * explosion.percent_left(),
+        )));
+        if explosion.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
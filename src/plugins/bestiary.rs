@@ -0,0 +1,422 @@
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+
+use crate::{
+    components::{AllyType, Clip, EnemyType, PlayerClass},
+    consts::{BUTTON_DEFAULT, SPLASH_DAMAGE, TRANSPARENT, VETERAN_KILLS, WALL_GROUP},
+    helpers::{back_on_esc, despawn_with, update_buttons},
+    resources::{Fonts, Sprites},
+    GameState,
+};
+
+const PREVIEW_SIZE: f32 = 48.0;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BestiaryTab {
+    Allies,
+    Enemies,
+    Classes,
+}
+
+impl BestiaryTab {
+    const ALL: [BestiaryTab; 3] = [
+        BestiaryTab::Allies,
+        BestiaryTab::Enemies,
+        BestiaryTab::Classes,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            BestiaryTab::Allies => "Allies",
+            BestiaryTab::Enemies => "Enemies",
+            BestiaryTab::Classes => "Classes",
+        }
+    }
+}
+
+#[derive(Component)]
+struct Bestiary;
+
+#[derive(Component)]
+struct BestiaryEntries;
+
+#[derive(Component)]
+struct TabButton(BestiaryTab);
+
+#[derive(Component)]
+struct TabLabel(BestiaryTab);
+
+#[derive(Component)]
+struct Preview {
+    clip: Clip,
+    frame: usize,
+    timer: Timer,
+}
+
+struct Entry {
+    name: String,
+    atlas: Handle<TextureAtlas>,
+    clip: Clip,
+    stats: String,
+    description: String,
+}
+
+pub struct BestiaryPlugin;
+
+impl Plugin for BestiaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(GameState::Info, setup_bestiary)
+            .add_exit_system(GameState::Info, despawn_with::<Bestiary>)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Info)
+                    .with_system(choose_tab)
+                    .with_system(show_entries)
+                    .with_system(animate_previews)
+                    .with_system(back_on_esc)
+                    .with_system(update_buttons)
+                    .into(),
+            );
+    }
+}
+
+fn setup_bestiary(mut commands: Commands, fonts: Res<Fonts>) {
+    commands.insert_resource(BestiaryTab::Allies);
+    commands
+        .spawn_bundle(Camera2dBundle::default())
+        .insert(Bestiary);
+    commands
+        .spawn_bundle(NodeBundle {
+            color: TRANSPARENT,
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::ColumnReverse,
+                padding: UiRect::all(Val::Px(12.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(Bestiary)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: TRANSPARENT,
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for tab in BestiaryTab::ALL {
+                        parent
+                            .spawn_bundle(ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(150.0), Val::Px(50.0)),
+                                    margin: UiRect::all(Val::Px(6.0)),
+                                    align_items: AlignItems::Center,
+                                    justify_content: JustifyContent::Center,
+                                    ..default()
+                                },
+                                color: BUTTON_DEFAULT,
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent
+                                    .spawn_bundle(TextBundle::from_section(
+                                        tab.name(),
+                                        TextStyle {
+                                            font: fonts.main.clone(),
+                                            font_size: 32.0,
+                                            color: Color::WHITE,
+                                        },
+                                    ))
+                                    .insert(TabLabel(tab));
+                            })
+                            .insert(TabButton(tab));
+                    }
+                });
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: TRANSPARENT,
+                    style: Style {
+                        flex_direction: FlexDirection::ColumnReverse,
+                        margin: UiRect::all(Val::Px(6.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .insert(BestiaryEntries);
+            parent.spawn_bundle(TextBundle::from_section(
+                "Esc to go back",
+                TextStyle {
+                    font: fonts.main.clone(),
+                    font_size: 18.0,
+                    color: Color::GRAY,
+                },
+            ));
+        });
+}
+
+fn choose_tab(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &TabButton), Changed<Interaction>>,
+) {
+    for (interaction, TabButton(tab)) in &buttons {
+        if *interaction == Interaction::Clicked {
+            commands.insert_resource(*tab);
+        }
+    }
+}
+
+fn entries(tab: BestiaryTab, sprites: &Sprites) -> Vec<Entry> {
+    match tab {
+        BestiaryTab::Allies => AllyType::SPAWN_WEIGHTS
+            .iter()
+            .filter_map(|(ally_type, _)| {
+                let stats = ally_type.stats()?;
+                let mut traits = Vec::new();
+                if let Some(arc) = ally_type.melee_arc(stats.attack_range) {
+                    traits.push(format!(
+                        "Cleaves a {:.0} degree arc",
+                        arc.angle.to_degrees()
+                    ));
+                }
+                if let Some(splash) = ally_type.splash() {
+                    traits.push(format!(
+                        "Fireballs hit everything within {:.0} for {:.0}% damage",
+                        splash.radius,
+                        SPLASH_DAMAGE * 100.0
+                    ));
+                }
+                let veteran_piercing = ally_type.piercing(VETERAN_KILLS);
+                if veteran_piercing > ally_type.piercing(0) {
+                    traits.push(format!(
+                        "After {} kills, arrows pierce {} more enemies",
+                        VETERAN_KILLS, veteran_piercing
+                    ));
+                }
+                if ally_type.revives() {
+                    traits.push("Revives fallen allies nearby".to_string());
+                }
+                traits.push(ally_type.description().to_string());
+                Some(Entry {
+                    name: ally_type.name().to_string(),
+                    atlas: sprites.ally(*ally_type),
+                    clip: ally_type.animations().walk,
+                    stats: format!(
+                        "HP {:.0}  DMG {:.0}  RNG {:.0}  SPD {:.2}/s  Push {:.0}  Spawn {:.0}%",
+                        stats.health,
+                        stats.damage,
+                        stats.attack_range,
+                        1.0 / stats.attack_cooldown,
+                        ally_type.knockback(),
+                        ally_type.spawn_chance() * 100.0,
+                    ),
+                    description: traits.join(". "),
+                })
+            })
+            .collect(),
+        BestiaryTab::Enemies => EnemyType::SPAWN_WEIGHTS
+            .iter()
+            .map(|(enemy_type, _)| {
+                let stats = enemy_type.stats();
+                let mut traits = vec![if enemy_type.is_ranged() {
+                    "Attacks from range".to_string()
+                } else {
+                    "Telegraphs its melee attacks".to_string()
+                }];
+                if let Some(turn_rate) = enemy_type.homing_turn_rate() {
+                    traits.push(format!(
+                        "Fireballs home in on their target at {:.1} rad/s",
+                        turn_rate
+                    ));
+                }
+                if enemy_type.collision_groups().filters & WALL_GROUP == 0 {
+                    traits.push("Passes through walls".to_string());
+                }
+                traits.push(enemy_type.description().to_string());
+                Entry {
+                    name: enemy_type.name().to_string(),
+                    atlas: sprites.enemy(*enemy_type),
+                    clip: enemy_type.animations().walk,
+                    stats: format!(
+                        "HP {:.0}  DMG {:.0}  RNG {:.0}  SPD {:.2}/s  Move {:.0}  Spawn {:.0}%",
+                        stats.health,
+                        stats.damage,
+                        stats.attack_range,
+                        1.0 / stats.attack_cooldown,
+                        enemy_type.speed(),
+                        enemy_type.spawn_chance() * 100.0,
+                    ),
+                    description: traits.join(". "),
+                }
+            })
+            .collect(),
+        BestiaryTab::Classes => PlayerClass::ALL
+            .iter()
+            .map(|class| {
+                let stats = class.stats();
+                let mut traits = Vec::new();
+                if let Some(arc) = class.melee_arc(stats.attack_range) {
+                    traits.push(format!(
+                        "Cleaves a {:.0} degree arc",
+                        arc.angle.to_degrees()
+                    ));
+                }
+                if class.piercing() > 0 {
+                    traits.push(format!("Arrows pierce {} more enemies", class.piercing()));
+                }
+                if let Some(chaining) = class.chaining() {
+                    traits.push(format!(
+                        "Flasks bounce to {} more enemies within {:.0}",
+                        chaining.remaining, chaining.range
+                    ));
+                }
+                if let Some(heal) = class.heal() {
+                    traits.push(format!(
+                        "Each throw heals the most wounded ally for {:.0}",
+                        heal
+                    ));
+                }
+                traits.push(class.description().replace('\n', " "));
+                Entry {
+                    name: class.name().to_string(),
+                    atlas: sprites.player.clone(),
+                    clip: AllyType::Player.animations().walk,
+                    stats: format!(
+                        "HP {:.0}  DMG {:.0}  RNG {:.0}  SPD {:.2}/s  Party radius {:.0}",
+                        stats.health,
+                        stats.damage,
+                        stats.attack_range,
+                        1.0 / stats.attack_cooldown,
+                        stats.party_radius,
+                    ),
+                    description: traits.join(". "),
+                }
+            })
+            .collect(),
+    }
+}
+
+fn show_entries(
+    mut commands: Commands,
+    tab: Res<BestiaryTab>,
+    fonts: Res<Fonts>,
+    sprites: Res<Sprites>,
+    texture_atlases: Res<Assets<TextureAtlas>>,
+    container: Query<Entity, With<BestiaryEntries>>,
+    mut tab_labels: Query<(&TabLabel, &mut Text)>,
+) {
+    if !tab.is_changed() {
+        return;
+    }
+    let container = match container.get_single() {
+        Ok(container) => container,
+        Err(_) => return,
+    };
+
+    for (TabLabel(label), mut text) in &mut tab_labels {
+        text.sections[0].style.color = if *label == *tab {
+            Color::GOLD
+        } else {
+            Color::WHITE
+        };
+    }
+
+    commands.entity(container).despawn_descendants();
+    commands.entity(container).with_children(|parent| {
+        for entry in entries(*tab, &sprites) {
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: UiColor(Color::rgba(0.0, 0.0, 0.0, 0.5)),
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        margin: UiRect::all(Val::Px(3.0)),
+                        padding: UiRect::all(Val::Px(4.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn_bundle(NodeBundle {
+                        color: TRANSPARENT,
+                        style: Style {
+                            size: Size::new(Val::Px(PREVIEW_SIZE), Val::Px(PREVIEW_SIZE)),
+                            overflow: Overflow::Hidden,
+                            margin: UiRect {
+                                right: Val::Px(12.0),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|window| {
+                        if let Some(atlas) = texture_atlases.get(&entry.atlas) {
+                            let scale = PREVIEW_SIZE / atlas.textures[0].size().y;
+                            window
+                                .spawn_bundle(ImageBundle {
+                                    image: UiImage(atlas.texture.clone()),
+                                    style: Style {
+                                        size: Size::new(
+                                            Val::Px(atlas.size.x * scale),
+                                            Val::Px(atlas.size.y * scale),
+                                        ),
+                                        flex_shrink: 0.0,
+                                        ..default()
+                                    },
+                                    ..default()
+                                })
+                                .insert(Preview {
+                                    clip: entry.clip,
+                                    frame: entry.clip.first,
+                                    timer: Timer::from_seconds(entry.clip.frame_time, true),
+                                });
+                        }
+                    });
+                    row.spawn_bundle(TextBundle::from_sections([
+                        TextSection::new(
+                            format!("{}\n", entry.name),
+                            TextStyle {
+                                font: fonts.main.clone(),
+                                font_size: 24.0,
+                                color: Color::WHITE,
+                            },
+                        ),
+                        TextSection::new(
+                            format!("{}\n", entry.stats),
+                            TextStyle {
+                                font: fonts.main.clone(),
+                                font_size: 16.0,
+                                color: Color::GOLD,
+                            },
+                        ),
+                        TextSection::new(
+                            entry.description,
+                            TextStyle {
+                                font: fonts.main.clone(),
+                                font_size: 16.0,
+                                color: Color::WHITE,
+                            },
+                        ),
+                    ]));
+                });
+        }
+    });
+}
+
+fn animate_previews(time: Res<Time>, mut previews: Query<(&mut Preview, &mut Style)>) {
+    for (mut preview, mut style) in &mut previews {
+        preview.timer.tick(time.delta());
+        if preview.timer.just_finished() {
+            preview.frame = if preview.frame >= preview.clip.last {
+                preview.clip.first
+            } else {
+                preview.frame + 1
+            };
+        }
+        style.position.left = Val::Px(-(preview.frame as f32) * PREVIEW_SIZE);
+    }
+}
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+use rand::prelude::*;
+
+use crate::{
+    components::{CameraController, EnemyType, IsDead, MainCamera, PartyRadius, Player},
+    consts::{
+        CAMERA_FOLLOW, CAMERA_MAX_SHAKE, CAMERA_MAX_ZOOM, CAMERA_MIN_ZOOM, CAMERA_ZOOM_SPEED,
+        SPRITE_SCALE, TRAUMA_DECAY, XEXTENT, YEXTENT,
+    },
+    helpers::despawn_with,
+    plugins::HitEvent,
+    GameState,
+};
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(GameState::InGame, spawn_camera)
+            .add_exit_system(GameState::InGame, despawn_with::<MainCamera>)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::InGame)
+                    .label("camera")
+                    .with_system(add_trauma)
+                    .with_system(update_camera)
+                    .into(),
+            );
+    }
+}
+
+fn spawn_camera(mut commands: Commands) {
+    commands
+        .spawn_bundle(Camera2dBundle {
+            transform: Transform::from_scale(Vec2::splat(CAMERA_MIN_ZOOM).extend(1.))
+                .with_translation(Vec3::Z * 999.9),
+            ..default()
+        })
+        .insert(CameraController {
+            focus: Vec2::ZERO,
+            zoom: CAMERA_MIN_ZOOM,
+            trauma: 0.0,
+        })
+        .insert(MainCamera);
+}
+
+fn add_trauma(
+    mut hit_events: EventReader<HitEvent>,
+    player: Query<Entity, With<Player>>,
+    mut camera: Query<&mut CameraController>,
+) {
+    let player = player.get_single().ok();
+    for hit in hit_events.iter() {
+        let trauma = if Some(hit.target) == player {
+            0.4
+        } else if hit.crit && hit.amount >= 30.0 {
+            0.2
+        } else {
+            continue;
+        };
+        for mut controller in &mut camera {
+            controller.trauma = (controller.trauma + trauma).min(1.0);
+        }
+    }
+}
+
+fn update_camera(
+    time: Res<Time>,
+    windows: Res<Windows>,
+    player: Query<(&Transform, &PartyRadius), With<Player>>,
+    enemies: Query<&Transform, (With<EnemyType>, Without<IsDead>)>,
+    mut camera: Query<
+        (&mut Transform, &mut CameraController),
+        (With<MainCamera>, Without<Player>, Without<EnemyType>),
+    >,
+) {
+    let (mut transform, mut controller) = match camera.get_single_mut() {
+        Ok(camera) => camera,
+        Err(_) => return,
+    };
+    let dt = time.delta_seconds();
+
+    if let Ok((player_transform, party_radius)) = player.get_single() {
+        let player_pos = player_transform.translation.truncate();
+        controller.focus = controller
+            .focus
+            .lerp(player_pos, 1.0 - (-CAMERA_FOLLOW * dt).exp());
+
+        let nearby = enemies
+            .iter()
+            .filter(|enemy| enemy.translation.truncate().distance(player_pos) <= 200.0)
+            .count();
+        let target_zoom = (CAMERA_MIN_ZOOM
+            + (party_radius.0 * SPRITE_SCALE - 80.0).max(0.0) / 800.0
+            + nearby as f32 * 0.005)
+            .clamp(CAMERA_MIN_ZOOM, CAMERA_MAX_ZOOM);
+        controller.zoom +=
+            (target_zoom - controller.zoom) * (1.0 - (-CAMERA_ZOOM_SPEED * dt).exp());
+    }
+
+    if let Some(window) = windows.get_primary() {
+        let half_view = Vec2::new(window.width(), window.height()) * controller.zoom / 2.0;
+        let min = Vec2::new(XEXTENT.0, YEXTENT.0) + half_view;
+        let max = Vec2::new(XEXTENT.1, YEXTENT.1) - half_view;
+        let center = Vec2::new(XEXTENT.0 + XEXTENT.1, YEXTENT.0 + YEXTENT.1) / 2.0;
+        controller.focus = Vec2::new(
+            if min.x < max.x {
+                controller.focus.x.clamp(min.x, max.x)
+            } else {
+                center.x
+            },
+            if min.y < max.y {
+                controller.focus.y.clamp(min.y, max.y)
+            } else {
+                center.y
+            },
+        );
+    }
+
+    controller.trauma = (controller.trauma - TRAUMA_DECAY * dt).max(0.0);
+    let mut rng = thread_rng();
+    let shake = Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0))
+        * controller.trauma.powi(2)
+        * CAMERA_MAX_SHAKE;
+
+    transform.translation = (controller.focus + shake).extend(transform.translation.z);
+    transform.scale = Vec2::splat(controller.zoom).extend(1.0);
+}
@@ -0,0 +1,107 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use bevy_rapier2d::prelude::*;
+use iyes_loopless::prelude::*;
+
+use crate::{components::ColliderOutline, resources::ShowColliders, GameState};
+
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShowColliders>().add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::InGame)
+                .with_system(toggle_colliders)
+                .with_system(draw_colliders)
+                .into(),
+        );
+    }
+}
+
+fn toggle_colliders(
+    mut commands: Commands,
+    keyboard: Res<Input<KeyCode>>,
+    mut show_colliders: ResMut<ShowColliders>,
+    outlines: Query<Entity, With<ColliderOutline>>,
+) {
+    if keyboard.just_pressed(KeyCode::F3) {
+        show_colliders.0 = !show_colliders.0;
+        if !show_colliders.0 {
+            for entity in &outlines {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+fn draw_colliders(
+    mut commands: Commands,
+    show_colliders: Res<ShowColliders>,
+    colliders: Query<(Entity, &Collider, ChangeTrackers<Collider>)>,
+) {
+    if !show_colliders.0 {
+        return;
+    }
+
+    for (entity, collider, tracker) in &colliders {
+        if !show_colliders.is_changed() && !tracker.is_added() {
+            continue;
+        }
+        let points = match collider_outline(collider) {
+            Some(points) => points,
+            None => continue,
+        };
+        let shape = shapes::Polygon {
+            points,
+            closed: true,
+        };
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn_bundle(GeometryBuilder::build_as(
+                    &shape,
+                    DrawMode::Stroke(StrokeMode::new(Color::CYAN, 0.5)),
+                    Transform::from_translation(Vec3::Z * 50.0),
+                ))
+                .insert(ColliderOutline);
+        });
+    }
+}
+
+fn collider_outline(collider: &Collider) -> Option<Vec<Vec2>> {
+    if let Some(ball) = collider.as_ball() {
+        return Some(arc(Vec2::ZERO, ball.radius(), 0.0, 2.0 * PI));
+    }
+    if let Some(cuboid) = collider.as_cuboid() {
+        let half = cuboid.half_extents();
+        return Some(vec![
+            Vec2::new(-half.x, -half.y),
+            Vec2::new(half.x, -half.y),
+            Vec2::new(half.x, half.y),
+            Vec2::new(-half.x, half.y),
+        ]);
+    }
+    if let Some(capsule) = collider.as_capsule() {
+        let (a, b) = (capsule.segment().a(), capsule.segment().b());
+        let angle = (b - a).y.atan2((b - a).x);
+        let mut points = arc(b, capsule.radius(), angle - FRAC_PI_2, angle + FRAC_PI_2);
+        points.extend(arc(
+            a,
+            capsule.radius(),
+            angle + FRAC_PI_2,
+            angle + 3.0 * FRAC_PI_2,
+        ));
+        return Some(points);
+    }
+    collider
+        .as_convex_polygon()
+        .map(|polygon| polygon.points().collect())
+}
+
+fn arc(center: Vec2, radius: f32, start: f32, end: f32) -> Vec<Vec2> {
+    (0..=12)
+        .map(|i| center + Vec2::from_angle(start + (end - start) * i as f32 / 12.0) * radius)
+        .collect()
+}
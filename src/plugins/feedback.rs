@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+
+use crate::{
+    components::{DamageKind, DamageNumber, EnemyType, HitFlash},
+    consts::{DAMAGE_NUMBER_LIFETIME, HIT_FLASH_DURATION},
+    helpers::despawn_with,
+    resources::{Fonts, Sounds},
+    GameState,
+};
+
+pub struct HitEvent {
+    pub target: Entity,
+    pub position: Vec2,
+    pub amount: f32,
+    pub kind: DamageKind,
+    pub crit: bool,
+}
+
+pub struct FeedbackPlugin;
+
+impl Plugin for FeedbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<HitEvent>()
+            .add_exit_system(GameState::InGame, despawn_with::<DamageNumber>)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::InGame)
+                    .with_system(spawn_hit_feedback)
+                    .with_system(float_damage_numbers)
+                    .with_system(fade_hit_flashes)
+                    .into(),
+            );
+    }
+}
+
+fn spawn_hit_feedback(
+    mut commands: Commands,
+    fonts: Res<Fonts>,
+    sounds: Res<Sounds>,
+    audio: Res<Audio>,
+    mut hit_events: EventReader<HitEvent>,
+    mut targets: Query<(&mut TextureAtlasSprite, Option<&EnemyType>)>,
+) {
+    for hit in hit_events.iter() {
+        let font_size = if hit.crit { 48.0 } else { 32.0 };
+        let text = if hit.crit {
+            format!("{:.0}!", hit.amount)
+        } else {
+            format!("{:.0}", hit.amount)
+        };
+        commands
+            .spawn_bundle(Text2dBundle {
+                text: Text::from_section(
+                    text,
+                    TextStyle {
+                        font: fonts.main.clone(),
+                        font_size,
+                        color: hit.kind.color(),
+                    },
+                )
+                .with_alignment(TextAlignment::CENTER),
+                transform: Transform::from_translation(hit.position.extend(20.0) + Vec3::Y * 8.0)
+                    .with_scale(Vec3::splat(0.25)),
+                ..default()
+            })
+            .insert(DamageNumber(Timer::from_seconds(
+                DAMAGE_NUMBER_LIFETIME,
+                false,
+            )));
+
+        if hit.crit {
+            audio.play_with_settings(
+                sounds.enemy_hit.clone(),
+                PlaybackSettings::ONCE.with_volume(0.3),
+            );
+        }
+
+        if let Ok((mut sprite, maybe_enemy)) = targets.get_mut(hit.target) {
+            let alpha = sprite.color.a();
+            sprite.color = if maybe_enemy.is_some() {
+                Color::rgba(4.0, 4.0, 4.0, alpha)
+            } else {
+                Color::rgba(1.0, 0.3, 0.3, alpha)
+            };
+            commands
+                .entity(hit.target)
+                .insert(HitFlash(Timer::from_seconds(HIT_FLASH_DURATION, false)));
+        }
+    }
+}
+
+fn float_damage_numbers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut numbers: Query<(Entity, &mut DamageNumber, &mut Transform, &mut Text)>,
+) {
+    for (entity, mut number, mut transform, mut text) in &mut numbers {
+        number.tick(time.delta());
+        transform.translation.y += 20.0 * time.delta_seconds();
+        for section in &mut text.sections {
+            section.style.color.set_a(1.0 - number.percent());
+        }
+        if number.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn fade_hit_flashes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flashing: Query<(Entity, &mut HitFlash, &mut TextureAtlasSprite)>,
+) {
+    for (entity, mut flash, mut sprite) in &mut flashing {
+        flash.tick(time.delta());
+        if flash.finished() {
+            let alpha = sprite.color.a();
+            sprite.color = Color::rgba(1.0, 1.0, 1.0, alpha);
+            commands.entity(entity).remove::<HitFlash>();
+        }
+    }
+}
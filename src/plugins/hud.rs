@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+
+use crate::{
+    components::{
+        DashCooldown, Health, InParty, IsDead, PartyRadius, Player, RallyCooldown, Revivable,
+    },
+    consts::TRANSPARENT,
+    helpers::despawn_with,
+    resources::{EnemyScale, Fonts, Formation, RunStats, Stance},
+    GameState,
+};
+
+#[derive(Component)]
+struct Hud;
+
+#[derive(Component)]
+struct PartySizeText;
+
+#[derive(Component)]
+struct FormationText;
+
+#[derive(Component)]
+struct StanceText;
+
+#[derive(Component)]
+struct AbilityText;
+
+#[derive(Component)]
+struct RunStatsText;
+
+#[derive(Component)]
+struct PlayerHealthFill;
+
+#[derive(Component)]
+struct PlayerHealthText;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStats>()
+            .add_enter_system(GameState::InGame, reset_run_stats)
+            .add_enter_system(GameState::InGame, spawn_hud)
+            .add_exit_system(GameState::InGame, despawn_with::<Hud>)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::InGame)
+                    .with_system(update_party_size_text)
+                    .with_system(update_formation_text)
+                    .with_system(update_stance_text)
+                    .with_system(update_ability_text)
+                    .with_system(tick_run_stats)
+                    .with_system(update_run_stats_text)
+                    .with_system(update_player_health)
+                    .into(),
+            );
+    }
+}
+
+fn spawn_hud(mut commands: Commands, fonts: Res<Fonts>) {
+    let text_style = TextStyle {
+        font: fonts.main.clone(),
+        font_size: 24.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn_bundle(NodeBundle {
+            color: TRANSPARENT,
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(10.0),
+                    left: Val::Px(10.0),
+                    ..default()
+                },
+                flex_direction: FlexDirection::ColumnReverse,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle::from_section("", text_style.clone()))
+                .insert(RunStatsText);
+            parent
+                .spawn_bundle(TextBundle::from_section("", text_style.clone()))
+                .insert(PartySizeText);
+            parent
+                .spawn_bundle(TextBundle::from_section("", text_style.clone()))
+                .insert(FormationText);
+            parent
+                .spawn_bundle(TextBundle::from_section("", text_style.clone()))
+                .insert(StanceText);
+            parent
+                .spawn_bundle(TextBundle::from_sections([
+                    TextSection::new("", text_style.clone()),
+                    TextSection::new("\n", text_style.clone()),
+                    TextSection::new("", text_style.clone()),
+                ]))
+                .insert(AbilityText);
+        })
+        .insert(Hud);
+
+    commands
+        .spawn_bundle(NodeBundle {
+            color: UiColor(Color::DARK_GRAY),
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    bottom: Val::Px(20.0),
+                    left: Val::Percent(30.0),
+                    ..default()
+                },
+                size: Size::new(Val::Percent(40.0), Val::Px(28.0)),
+                padding: UiRect::all(Val::Px(3.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: UiColor(Color::MAROON),
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position: UiRect::all(Val::Px(3.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn_bundle(NodeBundle {
+                            color: UiColor(Color::GREEN),
+                            style: Style {
+                                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .insert(PlayerHealthFill);
+                });
+            parent
+                .spawn_bundle(TextBundle::from_section("", text_style))
+                .insert(PlayerHealthText);
+        })
+        .insert(Hud);
+}
+
+fn reset_run_stats(mut commands: Commands) {
+    commands.insert_resource(RunStats::default());
+}
+
+fn tick_run_stats(time: Res<Time>, mut run_stats: ResMut<RunStats>) {
+    run_stats.elapsed += time.delta_seconds();
+}
+
+fn update_run_stats_text(
+    run_stats: Res<RunStats>,
+    enemy_scale: Res<EnemyScale>,
+    player: Query<&PartyRadius, With<Player>>,
+    mut text: Query<&mut Text, With<RunStatsText>>,
+) {
+    if let (Ok(party_radius), Ok(mut text)) = (player.get_single(), text.get_single_mut()) {
+        let seconds = run_stats.elapsed as u32;
+        text.sections[0].value = format!(
+            "Time: {:02}:{:02}  Kills: {}  Difficulty: x{:.2}  Radius: {:.1}",
+            seconds / 60,
+            seconds % 60,
+            run_stats.kills,
+            enemy_scale.0,
+            party_radius.0
+        );
+    }
+}
+
+fn update_player_health(
+    player: Query<&Health, With<Player>>,
+    mut fill: Query<(&mut Style, &mut UiColor), With<PlayerHealthFill>>,
+    mut text: Query<&mut Text, With<PlayerHealthText>>,
+) {
+    let health = match player.get_single() {
+        Ok(health) => health,
+        Err(_) => return,
+    };
+    let ratio = (health.0 / health.1).clamp(0.0, 1.0);
+    if let Ok((mut style, mut color)) = fill.get_single_mut() {
+        style.size.width = Val::Percent(ratio * 100.0);
+        color.0 = if ratio > 0.3 {
+            Color::GREEN
+        } else {
+            Color::RED
+        };
+    }
+    if let Ok(mut text) = text.get_single_mut() {
+        text.sections[0].value = format!("{:.0} / {:.0}", health.0.max(0.0), health.1);
+    }
+}
+
+fn update_party_size_text(
+    player: Query<&PartyRadius, With<Player>>,
+    party_members: Query<
+        (),
+        (
+            With<InParty>,
+            Without<Player>,
+            Or<(Without<IsDead>, With<Revivable>)>,
+        ),
+    >,
+    mut text: Query<&mut Text, With<PartySizeText>>,
+) {
+    if let (Ok(party_radius), Ok(mut text)) = (player.get_single(), text.get_single_mut()) {
+        let party_size = party_members.iter().count();
+        let capacity = party_radius.capacity();
+        text.sections[0].value = if party_size >= capacity {
+            format!("Party: {}/{} (full)", party_size, capacity)
+        } else {
+            format!("Party: {}/{}", party_size, capacity)
+        };
+    }
+}
+
+fn update_formation_text(
+    formation: Res<Formation>,
+    mut text: Query<&mut Text, With<FormationText>>,
+) {
+    if let Ok(mut text) = text.get_single_mut() {
+        text.sections[0].value = format!("Formation: {} [1-4]", formation.name());
+    }
+}
+
+fn update_stance_text(stance: Res<Stance>, mut text: Query<&mut Text, With<StanceText>>) {
+    if let Ok(mut text) = text.get_single_mut() {
+        text.sections[0].value = format!("Stance: {} [Z/X/C]", stance.name());
+    }
+}
+
+fn update_ability_text(
+    player: Query<(&DashCooldown, &RallyCooldown), With<Player>>,
+    mut text: Query<&mut Text, With<AbilityText>>,
+) {
+    if let (Ok((dash_cooldown, rally_cooldown)), Ok(mut text)) =
+        (player.get_single(), text.get_single_mut())
+    {
+        for (i, name, timer) in [
+            (0, "Dash [Space]", &dash_cooldown.0),
+            (2, "Rally [Q]", &rally_cooldown.0),
+        ] {
+            let section = &mut text.sections[i];
+            if timer.finished() {
+                section.value = format!("{}: ready", name);
+                section.style.color = Color::GREEN;
+            } else {
+                section.value = format!(
+                    "{}: {:.1}s",
+                    name,
+                    timer.duration().as_secs_f32() - timer.elapsed_secs()
+                );
+                section.style.color = Color::GRAY;
+            }
+        }
+    }
+}
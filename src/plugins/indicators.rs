@@ -0,0 +1,197 @@
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+
+use crate::{
+    components::{AllyType, EnemyType, Indicator, IsDead, MainCamera, Player},
+    consts::{INDICATOR_CLUSTER_RADIUS, INDICATOR_FADE_DISTANCE, INDICATOR_MARGIN},
+    resources::Fonts,
+    GameState,
+};
+
+pub struct IndicatorsPlugin;
+
+impl Plugin for IndicatorsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::InGame)
+                .after("camera")
+                .with_system(show_indicators)
+                .into(),
+        );
+    }
+}
+
+#[derive(Component)]
+struct IndicatorIcon;
+
+#[derive(Component)]
+struct IndicatorBadge;
+
+struct OffscreenUnit {
+    key: Entity,
+    edge: Vec2,
+    distance: f32,
+    atlas: Handle<TextureAtlas>,
+    is_ally: bool,
+    count: usize,
+}
+
+fn show_indicators(
+    mut commands: Commands,
+    fonts: Res<Fonts>,
+    windows: Res<Windows>,
+    camera: Query<&Transform, With<MainCamera>>,
+    units: Query<
+        (Entity, &Transform, &Handle<TextureAtlas>, Option<&AllyType>),
+        (
+            Or<(With<AllyType>, With<EnemyType>)>,
+            Without<Player>,
+            Without<IsDead>,
+            Without<Indicator>,
+            Without<IndicatorIcon>,
+        ),
+    >,
+    mut indicators: Query<
+        (Entity, &Indicator, &mut Transform, &mut Sprite, &Children),
+        (Without<MainCamera>, Without<AllyType>, Without<EnemyType>),
+    >,
+    mut icons: Query<(&mut Handle<TextureAtlas>, &mut TextureAtlasSprite), With<IndicatorIcon>>,
+    mut badges: Query<&mut Text, With<IndicatorBadge>>,
+) {
+    let (camera_transform, window) = match (camera.get_single(), windows.get_primary()) {
+        (Ok(camera_transform), Some(window)) => (camera_transform, window),
+        _ => return,
+    };
+    let zoom = camera_transform.scale.x;
+    let center = camera_transform.translation.truncate();
+    let half_view = Vec2::new(window.width(), window.height()) * zoom / 2.0;
+    let inner = half_view - Vec2::splat(INDICATOR_MARGIN * zoom);
+
+    let mut offscreen: Vec<OffscreenUnit> = Vec::new();
+    for (entity, transform, atlas, maybe_ally) in &units {
+        let offset = transform.translation.truncate() - center;
+        if offset.x.abs() <= half_view.x && offset.y.abs() <= half_view.y {
+            continue;
+        }
+        let t = (inner.x / offset.x.abs()).min(inner.y / offset.y.abs());
+        let edge = center + offset * t;
+        let distance = offset.length() - (offset * t).length();
+        let is_ally = maybe_ally.is_some();
+
+        if !is_ally {
+            if let Some(cluster) = offscreen.iter_mut().find(|unit| {
+                !unit.is_ally && unit.edge.distance(edge) <= INDICATOR_CLUSTER_RADIUS * zoom
+            }) {
+                cluster.count += 1;
+                cluster.distance = cluster.distance.min(distance);
+                continue;
+            }
+        }
+        offscreen.push(OffscreenUnit {
+            key: entity,
+            edge,
+            distance,
+            atlas: atlas.clone(),
+            is_ally,
+            count: 1,
+        });
+    }
+
+    for (entity, indicator, mut transform, mut sprite, children) in &mut indicators {
+        let index = match offscreen.iter().position(|unit| unit.key == indicator.0) {
+            Some(index) => index,
+            None => {
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+        };
+        let unit = offscreen.swap_remove(index);
+        let closeness = indicator_closeness(&unit);
+        *transform = indicator_transform(&unit, zoom, closeness);
+        sprite.color = indicator_color(&unit, closeness);
+        for child in children {
+            if let Ok((mut atlas, mut icon)) = icons.get_mut(*child) {
+                if *atlas != unit.atlas {
+                    *atlas = unit.atlas.clone();
+                }
+                icon.color.set_a(closeness);
+            }
+            if let Ok(mut text) = badges.get_mut(*child) {
+                let count = badge_text(&unit);
+                if text.sections[0].value != count {
+                    text.sections[0].value = count;
+                }
+            }
+        }
+    }
+
+    for unit in offscreen {
+        let closeness = indicator_closeness(&unit);
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: indicator_color(&unit, closeness),
+                    custom_size: Some(Vec2::splat(20.0)),
+                    ..default()
+                },
+                transform: indicator_transform(&unit, zoom, closeness),
+                ..default()
+            })
+            .insert(Indicator(unit.key))
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(SpriteSheetBundle {
+                        texture_atlas: unit.atlas.clone(),
+                        sprite: TextureAtlasSprite {
+                            color: Color::rgba(1.0, 1.0, 1.0, closeness),
+                            ..default()
+                        },
+                        transform: Transform::from_translation(Vec3::Z),
+                        ..default()
+                    })
+                    .insert(IndicatorIcon);
+                parent
+                    .spawn_bundle(Text2dBundle {
+                        text: Text::from_section(
+                            badge_text(&unit),
+                            TextStyle {
+                                font: fonts.main.clone(),
+                                font_size: 48.0,
+                                color: Color::WHITE,
+                            },
+                        )
+                        .with_alignment(TextAlignment::CENTER),
+                        transform: Transform::from_translation(Vec3::new(10.0, 10.0, 2.0))
+                            .with_scale(Vec3::splat(0.3)),
+                        ..default()
+                    })
+                    .insert(IndicatorBadge);
+            });
+    }
+}
+
+fn indicator_closeness(unit: &OffscreenUnit) -> f32 {
+    (1.0 - unit.distance / INDICATOR_FADE_DISTANCE).clamp(0.3, 1.0)
+}
+
+fn indicator_color(unit: &OffscreenUnit, closeness: f32) -> Color {
+    if unit.is_ally {
+        Color::rgba(0.3, 1.0, 0.3, closeness)
+    } else {
+        Color::rgba(1.0, 0.3, 0.3, closeness)
+    }
+}
+
+fn indicator_transform(unit: &OffscreenUnit, zoom: f32, closeness: f32) -> Transform {
+    Transform::from_translation(unit.edge.extend(50.0))
+        .with_scale(Vec3::splat(zoom * (0.6 + 0.4 * closeness)))
+}
+
+fn badge_text(unit: &OffscreenUnit) -> String {
+    if unit.count > 1 {
+        unit.count.to_string()
+    } else {
+        String::new()
+    }
+}
@@ -2,9 +2,10 @@ use bevy::{app::AppExit, audio::AudioSink, prelude::*};
 use iyes_loopless::prelude::*;
 
 use crate::{
-    consts::TRANSPARENT,
-    helpers::{button_pressed, despawn_with, go_to_state, update_buttons},
-    resources::{Fonts, MusicController, Sounds, Sprites},
+    components::PlayerClass,
+    consts::{BUTTON_DEFAULT, TRANSPARENT},
+    helpers::{back_on_esc, button_pressed, despawn_with, go_to_state, update_buttons},
+    resources::{Fonts, MusicController, Sounds},
     GameState,
 };
 
@@ -17,17 +18,24 @@ struct PlayButton;
 #[derive(Component)]
 struct InfoButton;
 
+#[derive(Component)]
+struct RecordsButton;
+
 #[derive(Component)]
 struct ExitButton;
 
 #[derive(Component)]
-struct Info;
+struct ClassSelect;
+
+#[derive(Component)]
+struct ClassButton(PlayerClass);
 
 pub struct MainMenuPlugin;
 
 impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<MusicController>()
+            .init_resource::<PlayerClass>()
             .add_enter_system(GameState::MainMenu, setup_menu)
             .add_enter_system(GameState::MainMenu, start_menu_music)
             .add_exit_system(GameState::MainMenu, despawn_with::<MainMenu>)
@@ -35,44 +43,26 @@ impl Plugin for MainMenuPlugin {
                 ConditionSet::new()
                     .run_in_state(GameState::MainMenu)
                     .with_system(
-                        go_to_state(GameState::InGame).run_if(button_pressed::<PlayButton>),
+                        go_to_state(GameState::ClassSelect).run_if(button_pressed::<PlayButton>),
                     )
                     .with_system(go_to_state(GameState::Info).run_if(button_pressed::<InfoButton>))
+                    .with_system(
+                        go_to_state(GameState::Records).run_if(button_pressed::<RecordsButton>),
+                    )
                     .with_system(exit.run_if(button_pressed::<ExitButton>))
                     .with_system(update_buttons)
                     .into(),
             )
-            .add_enter_system(GameState::Info, show_info)
+            .add_enter_system(GameState::ClassSelect, setup_class_select)
+            .add_exit_system(GameState::ClassSelect, despawn_with::<ClassSelect>)
             .add_system_set(
                 ConditionSet::new()
-                    .run_in_state(GameState::Info)
+                    .run_in_state(GameState::ClassSelect)
+                    .with_system(choose_class)
                     .with_system(back_on_esc)
+                    .with_system(update_buttons)
                     .into(),
-            )
-            .add_exit_system(GameState::Info, despawn_with::<Info>);
-    }
-}
-
-fn show_info(mut commands: Commands, sprites: Res<Sprites>) {
-    commands
-        .spawn_bundle(Camera2dBundle {
-            transform: Transform::from_scale(Vec2::splat(0.615).extend(1.0))
-                .with_translation(Vec3::Z * 999.9),
-            ..default()
-        })
-        .insert(Info);
-    commands
-        .spawn_bundle(SpriteBundle {
-            texture: sprites.info.clone(),
-            transform: Transform::from_translation(Vec3::Z * 10.0),
-            ..default()
-        })
-        .insert(Info);
-}
-
-fn back_on_esc(mut commands: Commands, kbd: Res<Input<KeyCode>>) {
-    if kbd.just_pressed(KeyCode::Escape) {
-        commands.insert_resource(NextState(GameState::MainMenu));
+            );
     }
 }
 
@@ -128,6 +118,16 @@ fn setup_menu(mut commands: Commands, fonts: Res<Fonts>) {
                 })
                 .insert(InfoButton);
 
+            parent
+                .spawn_bundle(ButtonBundle {
+                    style: button_style.clone(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn_bundle(TextBundle::from_section("Records", text_style.clone()));
+                })
+                .insert(RecordsButton);
+
             parent
                 .spawn_bundle(ButtonBundle {
                     style: button_style.clone(),
@@ -141,6 +141,105 @@ fn setup_menu(mut commands: Commands, fonts: Res<Fonts>) {
         .insert(MainMenu);
 }
 
+fn setup_class_select(mut commands: Commands, fonts: Res<Fonts>) {
+    commands
+        .spawn_bundle(Camera2dBundle::default())
+        .insert(ClassSelect);
+    commands
+        .spawn_bundle(NodeBundle {
+            color: TRANSPARENT,
+            style: Style {
+                size: Size::new(Val::Auto, Val::Auto),
+                margin: UiRect::all(Val::Auto),
+                align_self: AlignSelf::Center,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::ColumnReverse,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            let title_style = TextStyle {
+                font: fonts.main.clone(),
+                font_size: 40.0,
+                color: Color::WHITE,
+            };
+            let description_style = TextStyle {
+                font: fonts.main.clone(),
+                font_size: 18.0,
+                color: Color::WHITE,
+            };
+
+            parent.spawn_bundle(
+                TextBundle::from_section("Choose your class", title_style.clone()).with_style(
+                    Style {
+                        margin: UiRect::all(Val::Px(12.0)),
+                        ..default()
+                    },
+                ),
+            );
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: TRANSPARENT,
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for class in PlayerClass::ALL {
+                        let stats = class.stats();
+                        parent
+                            .spawn_bundle(ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(240.0), Val::Px(220.0)),
+                                    margin: UiRect::all(Val::Px(6.0)),
+                                    align_items: AlignItems::Center,
+                                    justify_content: JustifyContent::Center,
+                                    flex_direction: FlexDirection::ColumnReverse,
+                                    ..default()
+                                },
+                                color: BUTTON_DEFAULT,
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn_bundle(TextBundle::from_section(
+                                    class.name(),
+                                    title_style.clone(),
+                                ));
+                                parent.spawn_bundle(TextBundle::from_section(
+                                    format!(
+                                        "{}\n\nHealth: {}\nDamage: {}\nRange: {}\nParty radius: {}",
+                                        class.description(),
+                                        stats.health,
+                                        stats.damage,
+                                        stats.attack_range,
+                                        stats.party_radius,
+                                    ),
+                                    description_style.clone(),
+                                ));
+                            })
+                            .insert(ClassButton(class));
+                    }
+                });
+        })
+        .insert(ClassSelect);
+}
+
+fn choose_class(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &ClassButton), Changed<Interaction>>,
+) {
+    for (interaction, ClassButton(class)) in &buttons {
+        if *interaction == Interaction::Clicked {
+            commands.insert_resource(*class);
+            commands.insert_resource(NextState(GameState::InGame));
+        }
+    }
+}
+
 fn exit(mut ev: EventWriter<AppExit>) {
     ev.send(AppExit);
 }
@@ -0,0 +1,189 @@
+use bevy::{prelude::*, utils::HashSet};
+use iyes_loopless::prelude::*;
+
+use crate::{
+    components::{AllyType, EnemyType, InParty, IsDead, Player},
+    consts::{MINIMAP_WIDTH, XEXTENT, YEXTENT},
+    helpers::despawn_with,
+    GameState,
+};
+
+#[derive(Component)]
+struct Minimap;
+
+#[derive(Component)]
+struct MinimapDots;
+
+#[derive(Component)]
+struct MinimapDot(Entity);
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(GameState::InGame, spawn_minimap)
+            .add_exit_system(GameState::InGame, despawn_with::<Minimap>)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::InGame)
+                    .with_system(toggle_minimap)
+                    .with_system(update_minimap)
+                    .into(),
+            );
+    }
+}
+
+fn minimap_size() -> Vec2 {
+    let aspect = (YEXTENT.1 - YEXTENT.0) / (XEXTENT.1 - XEXTENT.0);
+    Vec2::new(MINIMAP_WIDTH, MINIMAP_WIDTH * aspect)
+}
+
+fn spawn_minimap(mut commands: Commands) {
+    let size = minimap_size();
+    commands
+        .spawn_bundle(NodeBundle {
+            color: UiColor(Color::rgba(1.0, 1.0, 1.0, 0.6)),
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    ..default()
+                },
+                size: Size::new(Val::Px(size.x + 4.0), Val::Px(size.y + 4.0)),
+                padding: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(Minimap)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: UiColor(Color::rgba(0.0, 0.0, 0.0, 0.7)),
+                    style: Style {
+                        size: Size::new(Val::Px(size.x), Val::Px(size.y)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .insert(MinimapDots);
+        });
+}
+
+fn toggle_minimap(keyboard: Res<Input<KeyCode>>, mut minimap: Query<&mut Style, With<Minimap>>) {
+    if !keyboard.just_pressed(KeyCode::M) {
+        return;
+    }
+    for mut style in &mut minimap {
+        style.display = match style.display {
+            Display::Flex => Display::None,
+            Display::None => Display::Flex,
+        };
+    }
+}
+
+fn dot_appearance(
+    maybe_player: Option<&Player>,
+    maybe_ally: Option<&AllyType>,
+    maybe_in_party: Option<&InParty>,
+    maybe_enemy: Option<&EnemyType>,
+) -> (Color, f32, bool) {
+    match (maybe_player, maybe_ally, maybe_enemy) {
+        (Some(_), ..) => (Color::WHITE, 5.0, false),
+        (None, Some(_), _) if maybe_in_party.is_some() => (Color::GREEN, 3.0, false),
+        (None, Some(_), _) => (Color::YELLOW, 3.0, false),
+        (None, None, Some(EnemyType::EvilWizard)) => (Color::PURPLE, 5.0, true),
+        (None, None, Some(EnemyType::Ghost)) => (Color::CYAN, 5.0, true),
+        _ => (Color::RED, 3.0, false),
+    }
+}
+
+fn dot_position(pos: Vec2, outer: f32) -> UiRect<Val> {
+    let size = minimap_size();
+    let x = (pos.x - XEXTENT.0) / (XEXTENT.1 - XEXTENT.0) * size.x;
+    let y = (pos.y - YEXTENT.0) / (YEXTENT.1 - YEXTENT.0) * size.y;
+    UiRect {
+        left: Val::Px((x - outer / 2.0).clamp(0.0, size.x - outer)),
+        bottom: Val::Px((y - outer / 2.0).clamp(0.0, size.y - outer)),
+        ..default()
+    }
+}
+
+fn update_minimap(
+    mut commands: Commands,
+    container: Query<Entity, With<MinimapDots>>,
+    mut dots: Query<(Entity, &MinimapDot, &mut Style, &mut UiColor)>,
+    units: Query<
+        (
+            Entity,
+            &Transform,
+            Option<&Player>,
+            Option<&AllyType>,
+            Option<&InParty>,
+            Option<&EnemyType>,
+        ),
+        (Or<(With<AllyType>, With<EnemyType>)>, Without<IsDead>),
+    >,
+) {
+    let container = match container.get_single() {
+        Ok(container) => container,
+        Err(_) => return,
+    };
+
+    let mut tracked = HashSet::new();
+    for (dot_entity, dot, mut style, mut color) in &mut dots {
+        let (_, transform, maybe_player, maybe_ally, maybe_in_party, maybe_enemy) =
+            match units.get(dot.0) {
+                Ok(unit) => unit,
+                Err(_) => {
+                    commands.entity(dot_entity).despawn_recursive();
+                    continue;
+                }
+            };
+        tracked.insert(dot.0);
+        let (dot_color, dot_size, elite) =
+            dot_appearance(maybe_player, maybe_ally, maybe_in_party, maybe_enemy);
+        let outer = if elite { dot_size + 2.0 } else { dot_size };
+        style.position = dot_position(transform.translation.truncate(), outer);
+        if !elite && color.0 != dot_color {
+            color.0 = dot_color;
+        }
+    }
+
+    commands.entity(container).with_children(|parent| {
+        for (entity, transform, maybe_player, maybe_ally, maybe_in_party, maybe_enemy) in &units {
+            if tracked.contains(&entity) {
+                continue;
+            }
+            let (color, dot_size, elite) =
+                dot_appearance(maybe_player, maybe_ally, maybe_in_party, maybe_enemy);
+            let outer = if elite { dot_size + 2.0 } else { dot_size };
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: UiColor(if elite { Color::WHITE } else { color }),
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position: dot_position(transform.translation.truncate(), outer),
+                        size: Size::new(Val::Px(outer), Val::Px(outer)),
+                        padding: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .insert(MinimapDot(entity))
+                .with_children(|dot| {
+                    if elite {
+                        dot.spawn_bundle(NodeBundle {
+                            color: UiColor(color),
+                            style: Style {
+                                size: Size::new(Val::Px(dot_size), Val::Px(dot_size)),
+                                ..default()
+                            },
+                            ..default()
+                        });
+                    }
+                });
+        }
+    });
+}
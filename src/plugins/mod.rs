@@ -9,3 +9,45 @@ pub use auto_battle::*;
 
 mod main_menu;
 pub use main_menu::*;
+
+mod hud;
+pub use hud::*;
+
+mod party;
+pub use party::*;
+
+mod targeting;
+pub use targeting::*;
+
+mod abilities;
+pub use abilities::*;
+
+mod revive;
+pub use revive::*;
+
+mod feedback;
+pub use feedback::*;
+
+mod debug;
+pub use debug::*;
+
+mod camera;
+pub use camera::*;
+
+mod indicators;
+pub use indicators::*;
+
+mod minimap;
+pub use minimap::*;
+
+mod roster;
+pub use roster::*;
+
+mod tooltip;
+pub use tooltip::*;
+
+mod bestiary;
+pub use bestiary::*;
+
+mod records;
+pub use records::*;
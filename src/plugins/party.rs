@@ -0,0 +1,290 @@
+use std::f32::consts::{FRAC_PI_3, TAU};
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use iyes_loopless::prelude::*;
+
+use crate::{
+    components::{
+        AllyType, AttackRange, EnemyType, Heading, InParty, IsDead, MainCamera, PartyRadius,
+        PinnedOffset, Player,
+    },
+    consts::{
+        AGGRESSIVE_LEASH, DEFENSIVE_SPREAD, FORMATION_STIFFNESS, PARTY_ACCELERATION,
+        PARTY_MAX_SPEED, SPRITE_SCALE,
+    },
+    helpers::{cursor_world_position, steer},
+    resources::{DraggingEntity, Formation, Stance},
+    GameState,
+};
+
+pub struct PartyPlugin;
+
+impl Plugin for PartyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Formation>()
+            .init_resource::<Stance>()
+            .init_resource::<DraggingEntity>()
+            .add_enter_system(GameState::InGame, reset_formation)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::InGame)
+                    .with_system(select_formation)
+                    .with_system(select_stance)
+                    .with_system(drag_party_members)
+                    .into(),
+            )
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::InGame)
+                    .after("first")
+                    .with_system(move_party_to_formation)
+                    .into(),
+            );
+    }
+}
+
+fn reset_formation(mut commands: Commands) {
+    commands.insert_resource(Formation::default());
+    commands.insert_resource(Stance::default());
+    commands.insert_resource(DraggingEntity::default());
+}
+
+fn select_formation(
+    mut commands: Commands,
+    mut formation: ResMut<Formation>,
+    pinned: Query<Entity, With<PinnedOffset>>,
+    keyboard: Res<Input<KeyCode>>,
+) {
+    *formation = if keyboard.just_pressed(KeyCode::Key1) {
+        Formation::Ring
+    } else if keyboard.just_pressed(KeyCode::Key2) {
+        Formation::Line
+    } else if keyboard.just_pressed(KeyCode::Key3) {
+        Formation::Wedge
+    } else if keyboard.just_pressed(KeyCode::Key4) {
+        Formation::Ranks
+    } else {
+        return;
+    };
+
+    for entity in &pinned {
+        commands.entity(entity).remove::<PinnedOffset>();
+    }
+}
+
+fn select_stance(
+    mut stance: ResMut<Stance>,
+    player: Query<&Transform, With<Player>>,
+    keyboard: Res<Input<KeyCode>>,
+) {
+    let new_stance = if keyboard.just_pressed(KeyCode::Z) {
+        Stance::Aggressive
+    } else if keyboard.just_pressed(KeyCode::X) {
+        Stance::Defensive
+    } else if keyboard.just_pressed(KeyCode::C) {
+        Stance::Hold(player.single().translation.truncate())
+    } else {
+        return;
+    };
+
+    *stance = if std::mem::discriminant(&*stance) == std::mem::discriminant(&new_stance) {
+        Stance::Follow
+    } else {
+        new_stance
+    };
+}
+
+fn drag_party_members(
+    mut commands: Commands,
+    mut dragging: ResMut<DraggingEntity>,
+    stance: Res<Stance>,
+    windows: Res<Windows>,
+    mouse: Res<Input<MouseButton>>,
+    camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    player: Query<(&Transform, &PartyRadius), With<Player>>,
+    party_members: Query<(Entity, &Transform), (With<InParty>, Without<Player>, Without<IsDead>)>,
+) {
+    let cursor = match camera.get_single() {
+        Ok((camera, camera_transform)) => {
+            match cursor_world_position(&windows, camera, camera_transform) {
+                Some(cursor) => cursor,
+                None => return,
+            }
+        }
+        Err(_) => return,
+    };
+    let member_under_cursor = || {
+        party_members
+            .iter()
+            .find(|(_, transform)| transform.translation.truncate().distance(cursor) < 16.0)
+            .map(|(entity, _)| entity)
+    };
+
+    if mouse.just_pressed(MouseButton::Left) {
+        dragging.0 = member_under_cursor();
+    }
+    if mouse.just_pressed(MouseButton::Right) {
+        if let Some(entity) = member_under_cursor() {
+            commands.entity(entity).remove::<PinnedOffset>();
+        }
+    }
+
+    if let Some(entity) = dragging.0 {
+        if !mouse.pressed(MouseButton::Left) || !party_members.contains(entity) {
+            dragging.0 = None;
+            return;
+        }
+
+        let (player_transform, party_radius) = player.single();
+        let anchor = stance.anchor(player_transform.translation.truncate());
+        let offset = (cursor - anchor).clamp_length_max(party_radius.0 * SPRITE_SCALE * 0.9);
+        commands.entity(entity).insert(PinnedOffset(offset));
+    }
+}
+
+fn move_party_to_formation(
+    time: Res<Time>,
+    formation: Res<Formation>,
+    stance: Res<Stance>,
+    player: Query<(&Transform, &Velocity, &Heading, &PartyRadius), With<Player>>,
+    mut party_members: Query<
+        (
+            Entity,
+            &Transform,
+            &mut Velocity,
+            &mut TextureAtlasSprite,
+            &AllyType,
+            &AttackRange,
+            Option<&PinnedOffset>,
+        ),
+        (With<InParty>, Without<Player>, Without<IsDead>),
+    >,
+    enemies: Query<&Transform, (With<EnemyType>, Without<AllyType>, Without<IsDead>)>,
+) {
+    let (player_transform, player_velocity, heading, party_radius) = player.single();
+    let player_pos = player_transform.translation.truncate();
+    let radius = party_radius.0 * SPRITE_SCALE;
+
+    let mut members = party_members
+        .iter()
+        .filter(|(.., pinned)| pinned.is_none())
+        .map(|(entity, _, _, _, ally_type, ..)| (entity, *ally_type))
+        .collect::<Vec<_>>();
+    members.sort_by_key(|(entity, _)| *entity);
+    let mut slots = formation_slots(*formation, &members, heading.0, radius)
+        .into_iter()
+        .zip(members.iter().map(|(entity, _)| *entity))
+        .map(|(slot, entity)| (entity, slot))
+        .collect::<Vec<_>>();
+
+    let anchor = stance.anchor(player_pos);
+    let (anchor_velocity, spread) = match *stance {
+        Stance::Hold(_) => (Vec2::ZERO, 1.0),
+        Stance::Defensive => (player_velocity.linvel, DEFENSIVE_SPREAD),
+        Stance::Follow | Stance::Aggressive => (player_velocity.linvel, 1.0),
+    };
+    slots.extend(
+        party_members
+            .iter()
+            .filter_map(|(entity, .., pinned)| pinned.map(|offset| (entity, offset.0 / spread))),
+    );
+
+    for (entity, slot) in slots {
+        let (_, transform, mut velocity, mut texture_atlas_sprite, _, range, _) =
+            party_members.get_mut(entity).unwrap();
+        let pos = transform.translation.truncate();
+
+        let chase_target = if *stance == Stance::Aggressive {
+            enemies
+                .iter()
+                .map(|enemy_transform| enemy_transform.translation.truncate())
+                .filter(|enemy_pos| enemy_pos.distance(player_pos) <= radius * AGGRESSIVE_LEASH)
+                .min_by(|a, b| a.distance(pos).total_cmp(&b.distance(pos)))
+        } else {
+            None
+        };
+
+        let desired = match chase_target {
+            Some(enemy_pos) => {
+                let target = enemy_pos + (pos - enemy_pos).normalize_or_zero() * range.0 * 0.8;
+                (target - pos) * FORMATION_STIFFNESS
+            }
+            None => anchor_velocity + (anchor + slot * spread - pos) * FORMATION_STIFFNESS,
+        }
+        .clamp_length_max(PARTY_MAX_SPEED.max(anchor_velocity.length() * 1.2));
+        velocity.linvel = steer(
+            velocity.linvel,
+            desired,
+            PARTY_ACCELERATION * time.delta_seconds(),
+        );
+
+        if desired.length() >= 5.0 {
+            texture_atlas_sprite.flip_x = desired.x < 0.0;
+        }
+    }
+}
+
+fn formation_slots(
+    formation: Formation,
+    members: &[(Entity, AllyType)],
+    forward: Vec2,
+    radius: f32,
+) -> Vec<Vec2> {
+    let count = members.len();
+    let right = -forward.perp();
+    let spacing = 24.0;
+
+    match formation {
+        Formation::Ring => arc_slots(count, Vec2::Y, TAU, radius * 0.7),
+        Formation::Line => (0..count)
+            .map(|i| {
+                let offset = (i as f32 - (count as f32 - 1.0) / 2.0) * spacing;
+                (forward * radius * 0.5 + right * offset).clamp_length_max(radius * 0.9)
+            })
+            .collect(),
+        Formation::Wedge => (0..count)
+            .map(|i| {
+                let rank = (i / 2 + 1) as f32;
+                let side = if i % 2 == 0 { 1.0 } else { -1.0 };
+                ((-forward + right * side) * rank * spacing).clamp_length_max(radius * 0.9)
+            })
+            .collect(),
+        Formation::Ranks => {
+            let ranged = members
+                .iter()
+                .filter(|(_, ally_type)| ally_type.is_ranged())
+                .count();
+            let mut front = arc_slots(count - ranged, forward, FRAC_PI_3 * 2.0, radius * 0.6);
+            let mut back = arc_slots(ranged, -forward, FRAC_PI_3 * 2.0, radius * 0.6);
+            members
+                .iter()
+                .map(|(_, ally_type)| {
+                    if ally_type.is_ranged() {
+                        back.pop().unwrap()
+                    } else {
+                        front.pop().unwrap()
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+fn arc_slots(count: usize, dir: Vec2, spread: f32, radius: f32) -> Vec<Vec2> {
+    let step = if spread >= TAU {
+        spread / count as f32
+    } else if count > 1 {
+        spread / (count - 1) as f32
+    } else {
+        0.0
+    };
+    let start = if spread >= TAU || count <= 1 {
+        0.0
+    } else {
+        -spread / 2.0
+    };
+    (0..count)
+        .map(|i| Vec2::from_angle(start + step * i as f32).rotate(dir) * radius)
+        .collect()
+}
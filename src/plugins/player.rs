@@ -1,5 +1,3 @@
-use std::iter;
-
 use bevy::{audio::AudioSink, prelude::*};
 use bevy_prototype_lyon::prelude::*;
 use bevy_rapier2d::prelude::*;
@@ -7,11 +5,15 @@ use iyes_loopless::prelude::*;
 
 use crate::{
     components::{
-        AllyBundle, AllyType, AnimationTimer, AttackRange, AttackTimer, Damage, EnemyType, Health,
-        InParty, Indicator, IndicatorEntity, IsDead, PartyRadius, Player, PlayerBundle, Speed,
+        AllyBundle, AllyType, AnimationController, AttackRange, AttackTimer, Damage, DashCooldown,
+        Dashing, EnemyType, Heading, Health, InParty, IsDead, PartyCircle, PartyRadius, Player,
+        PlayerBundle, PlayerClass, RallyCooldown, Revivable, Speed,
+    },
+    consts::{
+        ALLY_GROUPS, DASH_COOLDOWN, ENEMY_ACCELERATION, PLAYER_ACCELERATION, RALLY_COOLDOWN,
+        SPRITE_SCALE, UNIT_DAMPING,
     },
-    consts::SPRITE_SCALE,
-    helpers::{check_player_death, player_death_animation},
+    helpers::{player_death_animation, ready_timer, steer},
     resources::{EnemyScale, EnemySpawnChance, MusicController, Sounds, Sprites},
     GameState,
 };
@@ -29,17 +31,8 @@ impl Plugin for PlayerPlugin {
                     .with_system(update_circle)
                     .with_system(add_to_party)
                     .with_system(move_enemies_towards_closest_ally)
-                    .with_system(check_player_death)
-                    .with_system(show_indicators)
                     .with_system(player_death_animation)
                     .into(),
-            )
-            .add_system_set(
-                ConditionSet::new()
-                    .run_in_state(GameState::InGame)
-                    .after("first")
-                    .with_system(keep_allies_in_circle)
-                    .into(),
             );
     }
 }
@@ -47,20 +40,23 @@ impl Plugin for PlayerPlugin {
 fn spawn_player(
     mut commands: Commands,
     sprites: Res<Sprites>,
+    class: Res<PlayerClass>,
     mut spawn_chance: ResMut<EnemySpawnChance>,
     mut enemy_scale: ResMut<EnemyScale>,
 ) {
     spawn_chance.0 = 0.8;
     enemy_scale.0 = 1.0;
+    let stats = class.stats();
     commands
         .spawn_bundle(PlayerBundle {
-            party_radius: PartyRadius(40.0),
+            party_radius: PartyRadius(stats.party_radius),
+            class: *class,
             ally: AllyBundle {
                 ally_type: AllyType::Player,
-                attack_range: AttackRange(60.0),
-                attack_timer: AttackTimer(Timer::from_seconds(0.5, true)),
-                damage: Damage(10.0),
-                health: Health(100.0, 100.0),
+                attack_range: AttackRange(stats.attack_range),
+                attack_timer: AttackTimer(Timer::from_seconds(stats.attack_cooldown, true)),
+                damage: Damage(stats.damage),
+                health: Health(stats.health, stats.health),
                 sprite: SpriteSheetBundle {
                     texture_atlas: sprites.player.clone(),
                     transform: Transform::from_scale(Vec3::splat(SPRITE_SCALE))
@@ -71,24 +67,29 @@ fn spawn_player(
             },
             ..default()
         })
-        .insert(AnimationTimer(Timer::from_seconds(0.115, true)))
-        .insert(Collider::cuboid(8.0, 8.0))
+        .insert(AnimationController::new(AllyType::Player.animations()))
+        .insert(DashCooldown(ready_timer(DASH_COOLDOWN)))
+        .insert(RallyCooldown(ready_timer(RALLY_COOLDOWN)))
+        .insert(AllyType::Player.collider())
+        .insert(ALLY_GROUPS)
+        .insert(ColliderMassProperties::Density(AllyType::Player.density()))
+        .insert(Damping {
+            linear_damping: UNIT_DAMPING,
+            angular_damping: 0.0,
+        })
         .insert(LockedAxes::ROTATION_LOCKED)
         .with_children(|parent| {
             let shape = shapes::Circle { ..default() };
-            parent.spawn_bundle(GeometryBuilder::build_as(
-                &shape,
-                DrawMode::Stroke(StrokeMode {
-                    color: Color::PURPLE,
-                    options: StrokeOptions::default().with_line_width(1.0),
-                }),
-                Transform::default(),
-            ));
-            parent.spawn_bundle(Camera2dBundle {
-                transform: Transform::from_scale(Vec2::splat(0.25).extend(1.))
-                    .with_translation(Vec3::Z * 997.9),
-                ..default()
-            });
+            parent
+                .spawn_bundle(GeometryBuilder::build_as(
+                    &shape,
+                    DrawMode::Stroke(StrokeMode {
+                        color: Color::PURPLE,
+                        options: StrokeOptions::default().with_line_width(1.0),
+                    }),
+                    Transform::default(),
+                ))
+                .insert(PartyCircle);
         });
 }
 
@@ -108,49 +109,47 @@ fn start_game_music(
 }
 
 fn handle_inputs(
+    time: Res<Time>,
     mut player: Query<
-        (&mut Velocity, &mut AnimationTimer, &mut TextureAtlasSprite),
-        (With<Player>, Without<IsDead>),
-    >,
-    mut party_members: Query<
-        (&mut Velocity, &mut AnimationTimer, &mut TextureAtlasSprite),
-        (With<InParty>, Without<Player>),
+        (&mut Velocity, &mut Heading, &mut TextureAtlasSprite),
+        (With<Player>, Without<IsDead>, Without<Dashing>),
     >,
     keyboard: Res<Input<KeyCode>>,
 ) {
-    if let Ok((velocity, animation_timer, texture_atlas_sprite)) = player.get_single_mut() {
-        for (mut velocity, mut animation_timer, mut texture_atlas_sprite) in
-            iter::once((velocity, animation_timer, texture_atlas_sprite))
-                .chain(party_members.iter_mut())
-        {
-            velocity.linvel = Vec2::ZERO;
-            if keyboard.pressed(KeyCode::W) {
-                velocity.linvel.y += 1.;
-            }
-            if keyboard.pressed(KeyCode::S) {
-                velocity.linvel.y -= 1.;
-            }
-            if keyboard.pressed(KeyCode::D) {
-                velocity.linvel.x += 1.;
-                texture_atlas_sprite.flip_x = false;
-            }
-            if keyboard.pressed(KeyCode::A) {
-                velocity.linvel.x -= 1.;
-                texture_atlas_sprite.flip_x = true;
-            }
+    if let Ok((mut velocity, mut heading, mut texture_atlas_sprite)) = player.get_single_mut() {
+        let mut direction = Vec2::ZERO;
+        if keyboard.pressed(KeyCode::W) {
+            direction.y += 1.;
+        }
+        if keyboard.pressed(KeyCode::S) {
+            direction.y -= 1.;
+        }
+        if keyboard.pressed(KeyCode::D) {
+            direction.x += 1.;
+            texture_atlas_sprite.flip_x = false;
+        }
+        if keyboard.pressed(KeyCode::A) {
+            direction.x -= 1.;
+            texture_atlas_sprite.flip_x = true;
+        }
 
-            velocity.linvel = velocity.linvel.normalize_or_zero() * 200.0;
+        let desired = direction.normalize_or_zero() * 200.0;
+        velocity.linvel = steer(
+            velocity.linvel,
+            desired,
+            PLAYER_ACCELERATION * time.delta_seconds(),
+        );
 
-            if velocity.linvel == Vec2::ZERO {
-                animation_timer.pause();
-            } else {
-                animation_timer.unpause();
-            }
+        if desired != Vec2::ZERO {
+            heading.0 = desired.normalize();
         }
     }
 }
 
-fn update_circle(player: Query<&PartyRadius, With<Player>>, mut path: Query<&mut Path>) {
+fn update_circle(
+    player: Query<&PartyRadius, With<Player>>,
+    mut path: Query<&mut Path, With<PartyCircle>>,
+) {
     let party_radius = player.single();
     let mut path = path.single_mut();
 
@@ -165,119 +164,51 @@ fn update_circle(player: Query<&PartyRadius, With<Player>>, mut path: Query<&mut
 fn add_to_party(
     mut commands: Commands,
     player: Query<(&Transform, &PartyRadius), With<Player>>,
-    entities: Query<(Entity, &Transform), (Without<InParty>, Without<Player>, With<AllyType>)>,
-) {
-    let (player_transform, party_radius) = player.single();
-    for (entity, transform) in &entities {
-        if player_transform
-            .translation
-            .truncate()
-            .distance(transform.translation.truncate())
-            < party_radius.0 * SPRITE_SCALE
-        {
-            commands.entity(entity).insert(InParty);
-        }
-    }
-}
-
-pub fn show_indicators(
-    mut commands: Commands,
-    player: Query<&Transform, With<Player>>,
-    mut entities: Query<
+    party_members: Query<
+        (),
         (
-            &Transform,
-            &mut IndicatorEntity,
-            Option<&AllyType>,
-            Option<&EnemyType>,
+            With<InParty>,
+            Without<Player>,
+            Or<(Without<IsDead>, With<Revivable>)>,
         ),
-        (Without<Player>, Without<Indicator>),
     >,
-    mut indicators: Query<
-        &mut Transform,
-        (With<Indicator>, Without<Player>, Without<IndicatorEntity>),
+    entities: Query<
+        (Entity, &Transform),
+        (
+            Without<InParty>,
+            Without<Player>,
+            With<AllyType>,
+            Without<IsDead>,
+        ),
     >,
-) {
-    let player_transform = player.single();
-    for (transform, mut indicator_entity, maybe_ally, maybe_enemy) in &mut entities {
-        if !(maybe_ally.is_some() || maybe_enemy.is_some()) {
-            continue;
-        }
-        let indicator_transform = Transform::from_translation(
-            player_transform.translation
-                + ((transform.translation.truncate() - player_transform.translation.truncate())
-                    .normalize()
-                    * 250.0)
-                    .extend(10.0),
-        );
-        let dist = player_transform
-            .translation
-            .truncate()
-            .distance(transform.translation.truncate());
-
-        if dist < 250.0 && indicator_entity.is_some() {
-            commands
-                .entity(indicator_entity.0.unwrap())
-                .despawn_recursive();
-            indicator_entity.0 = None;
-        } else if dist >= 250.0 {
-            if indicator_entity.is_none() {
-                indicator_entity.0 = Some(
-                    commands
-                        .spawn_bundle(SpriteBundle {
-                            transform: indicator_transform,
-                            sprite: Sprite {
-                                color: if maybe_ally.is_some() {
-                                    Color::GREEN
-                                } else {
-                                    Color::RED
-                                },
-                                custom_size: Some(Vec2::new(10.0, 10.0)),
-                                ..default()
-                            },
-                            ..default()
-                        })
-                        .insert(Indicator)
-                        .id(),
-                );
-            } else {
-                if let Ok(mut transform) = indicators.get_mut(indicator_entity.unwrap()) {
-                    *transform = indicator_transform;
-                }
-            }
-        }
-    }
-}
-
-fn keep_allies_in_circle(
-    player: Query<(&Transform, &PartyRadius), With<Player>>,
-    mut party_members: Query<(&Transform, &mut Velocity), With<InParty>>,
 ) {
     let (player_transform, party_radius) = player.single();
-    for (transform, mut vel) in &mut party_members {
+    let mut party_size = party_members.iter().count();
+    for (entity, transform) in &entities {
+        if party_size >= party_radius.capacity() {
+            break;
+        }
         if player_transform
             .translation
             .truncate()
             .distance(transform.translation.truncate())
-            > party_radius.0 * SPRITE_SCALE
+            < party_radius.0 * SPRITE_SCALE
         {
-            vel.linvel = (player_transform.translation.truncate()
-                - transform.translation.truncate())
-            .normalize()
-                * 400.0;
-        } else {
-            vel.linvel += (player_transform.translation.truncate()
-                - transform.translation.truncate())
-            .normalize()
-                * 10.0;
+            commands.entity(entity).insert(InParty);
+            party_size += 1;
         }
     }
 }
 
 fn move_enemies_towards_closest_ally(
-    allies: Query<&Transform, With<AllyType>>,
-    mut enemies: Query<(&Transform, &mut Velocity, &Speed), With<EnemyType>>,
+    time: Res<Time>,
+    allies: Query<&Transform, (With<AllyType>, Without<IsDead>)>,
+    mut enemies: Query<
+        (&Transform, &mut Velocity, &mut TextureAtlasSprite, &Speed),
+        (With<EnemyType>, Without<IsDead>),
+    >,
 ) {
-    for (enemy_transform, mut velocity, speed) in &mut enemies {
+    for (enemy_transform, mut velocity, mut sprite, speed) in &mut enemies {
         let mut closest = (f32::MAX, Transform::default());
         for ally_transform in &allies {
             let dist = enemy_transform
@@ -288,8 +219,16 @@ fn move_enemies_towards_closest_ally(
                 closest = (dist, *ally_transform);
             }
         }
-        velocity.linvel =
-            (closest.1.translation.truncate() - enemy_transform.translation.truncate()).normalize()
-                * speed.0;
+        let desired = (closest.1.translation.truncate() - enemy_transform.translation.truncate())
+            .normalize()
+            * speed.0;
+        velocity.linvel = steer(
+            velocity.linvel,
+            desired,
+            ENEMY_ACCELERATION * time.delta_seconds(),
+        );
+        if desired.x != 0.0 {
+            sprite.flip_x = desired.x < 0.0;
+        }
     }
 }
@@ -0,0 +1,528 @@
+use std::{
+    cmp::Ordering,
+    fs::{self, OpenOptions},
+    io::Write,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+use rand::prelude::*;
+
+use crate::{
+    components::{AllyType, InParty, IsDead, LastHitBy, Player, PlayerClass},
+    consts::{BUTTON_DEFAULT, MAX_RECORD_ROWS, RECORDS_FILE, TRANSPARENT},
+    helpers::{back_on_esc, despawn_with, update_buttons},
+    resources::{CombatRng, Fonts, PendingSeed, RunSeed, RunStats, SpawnRng},
+    GameState,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Date,
+    Seed,
+    Class,
+    Duration,
+    Kills,
+    Party,
+    Cause,
+}
+
+impl Column {
+    const ALL: [Column; 7] = [
+        Column::Date,
+        Column::Seed,
+        Column::Class,
+        Column::Duration,
+        Column::Kills,
+        Column::Party,
+        Column::Cause,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Column::Date => "Date",
+            Column::Seed => "Seed",
+            Column::Class => "Class",
+            Column::Duration => "Time",
+            Column::Kills => "Kills",
+            Column::Party => "Party",
+            Column::Cause => "Cause of death",
+        }
+    }
+
+    fn width(&self) -> f32 {
+        match self {
+            Column::Date => 150.0,
+            Column::Seed => 190.0,
+            Column::Class => 90.0,
+            Column::Duration => 70.0,
+            Column::Kills => 60.0,
+            Column::Party => 330.0,
+            Column::Cause => 140.0,
+        }
+    }
+}
+
+struct RunRecord {
+    timestamp: u64,
+    seed: u64,
+    class: PlayerClass,
+    duration: f32,
+    kills: u32,
+    party: Vec<AllyType>,
+    cause: String,
+}
+
+impl RunRecord {
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{:.1}\t{}\t{}\t{}",
+            self.timestamp,
+            self.seed,
+            self.class.name(),
+            self.duration,
+            self.kills,
+            self.party
+                .iter()
+                .map(|ally_type| ally_type.name())
+                .collect::<Vec<_>>()
+                .join(","),
+            self.cause
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let timestamp = fields.next()?.parse().ok()?;
+        let seed = fields.next()?.parse().ok()?;
+        let class_name = fields.next()?;
+        let class = *PlayerClass::ALL
+            .iter()
+            .find(|class| class.name() == class_name)?;
+        let duration = fields.next()?.parse().ok()?;
+        let kills = fields.next()?.parse().ok()?;
+        let party = fields
+            .next()?
+            .split(',')
+            .filter_map(|name| {
+                AllyType::SPAWN_WEIGHTS
+                    .iter()
+                    .map(|(ally_type, _)| *ally_type)
+                    .find(|ally_type| ally_type.name() == name)
+            })
+            .collect();
+        let cause = fields.next()?.to_string();
+        Some(Self {
+            timestamp,
+            seed,
+            class,
+            duration,
+            kills,
+            party,
+            cause,
+        })
+    }
+
+    fn cell(&self, column: Column) -> String {
+        match column {
+            Column::Date => format_date(self.timestamp),
+            Column::Seed => self.seed.to_string(),
+            Column::Class => self.class.name().to_string(),
+            Column::Duration => format!(
+                "{:02}:{:02}",
+                self.duration as u32 / 60,
+                self.duration as u32 % 60
+            ),
+            Column::Kills => self.kills.to_string(),
+            Column::Party => {
+                let mut counts: Vec<(AllyType, u32)> = Vec::new();
+                for ally_type in &self.party {
+                    match counts.iter_mut().find(|(other, _)| other == ally_type) {
+                        Some((_, count)) => *count += 1,
+                        None => counts.push((*ally_type, 1)),
+                    }
+                }
+                if counts.is_empty() {
+                    "Alone".to_string()
+                } else {
+                    counts
+                        .iter()
+                        .map(|(ally_type, count)| format!("{} {}", count, ally_type.name()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }
+            }
+            Column::Cause => self.cause.clone(),
+        }
+    }
+
+    fn compare(&self, other: &Self, column: Column) -> Ordering {
+        match column {
+            Column::Date => self.timestamp.cmp(&other.timestamp),
+            Column::Seed => self.seed.cmp(&other.seed),
+            Column::Class => self.class.name().cmp(other.class.name()),
+            Column::Duration => self.duration.total_cmp(&other.duration),
+            Column::Kills => self.kills.cmp(&other.kills),
+            Column::Party => self.party.len().cmp(&other.party.len()),
+            Column::Cause => self.cause.cmp(&other.cause),
+        }
+    }
+}
+
+// Days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+fn format_date(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    let seconds = timestamp % 86400;
+    format!(
+        "{}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60
+    )
+}
+
+fn load_records() -> Vec<RunRecord> {
+    fs::read_to_string(RECORDS_FILE)
+        .map(|contents| contents.lines().filter_map(RunRecord::from_line).collect())
+        .unwrap_or_default()
+}
+
+struct Records(Vec<RunRecord>);
+
+struct RecordSort {
+    column: Column,
+    descending: bool,
+}
+
+#[derive(Component)]
+struct RecordsScreen;
+
+#[derive(Component)]
+struct RecordRows;
+
+#[derive(Component)]
+struct SortButton(Column);
+
+#[derive(Component)]
+struct ReplayButton(u64, PlayerClass);
+
+pub struct RecordsPlugin;
+
+impl Plugin for RecordsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunSeed>()
+            .init_resource::<PendingSeed>()
+            .add_enter_system(GameState::InGame, seed_run)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::InGame)
+                    .with_system(save_record)
+                    .into(),
+            )
+            .add_enter_system(GameState::Records, setup_records)
+            .add_exit_system(GameState::Records, despawn_with::<RecordsScreen>)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::Records)
+                    .with_system(sort_records)
+                    .with_system(show_records)
+                    .with_system(replay_seed)
+                    .with_system(back_on_esc)
+                    .with_system(update_buttons)
+                    .into(),
+            );
+    }
+}
+
+fn seed_run(
+    mut pending_seed: ResMut<PendingSeed>,
+    mut run_seed: ResMut<RunSeed>,
+    mut spawn_rng: ResMut<SpawnRng>,
+    mut combat_rng: ResMut<CombatRng>,
+) {
+    let seed = pending_seed.0.take().unwrap_or_else(random);
+    run_seed.0 = seed;
+    *spawn_rng = SpawnRng::from_seed(seed);
+    combat_rng.0 = StdRng::from_entropy();
+}
+
+fn save_record(
+    run_seed: Res<RunSeed>,
+    run_stats: Res<RunStats>,
+    class: Res<PlayerClass>,
+    player: Query<Option<&LastHitBy>, (With<Player>, Added<IsDead>)>,
+    party_members: Query<&AllyType, (With<InParty>, Without<Player>, Without<IsDead>)>,
+) {
+    let last_hit = match player.get_single() {
+        Ok(last_hit) => last_hit,
+        Err(_) => return,
+    };
+    let record = RunRecord {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs()),
+        seed: run_seed.0,
+        class: *class,
+        duration: run_stats.elapsed,
+        kills: run_stats.kills,
+        party: party_members.iter().copied().collect(),
+        cause: last_hit
+            .and_then(|last_hit| last_hit.1)
+            .map_or("Unknown", |enemy_type| enemy_type.name())
+            .to_string(),
+    };
+
+    let result = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(RECORDS_FILE)
+        .and_then(|mut file| writeln!(file, "{}", record.to_line()));
+    if let Err(err) = result {
+        warn!("Failed to save run record: {}", err);
+    }
+}
+
+fn setup_records(mut commands: Commands, fonts: Res<Fonts>) {
+    commands.insert_resource(Records(load_records()));
+    commands.insert_resource(RecordSort {
+        column: Column::Date,
+        descending: true,
+    });
+    commands
+        .spawn_bundle(Camera2dBundle::default())
+        .insert(RecordsScreen);
+    commands
+        .spawn_bundle(NodeBundle {
+            color: TRANSPARENT,
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::ColumnReverse,
+                padding: UiRect::all(Val::Px(12.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(RecordsScreen)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle::from_section(
+                "Records",
+                TextStyle {
+                    font: fonts.main.clone(),
+                    font_size: 40.0,
+                    color: Color::WHITE,
+                },
+            ));
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: TRANSPARENT,
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        margin: UiRect::all(Val::Px(6.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for column in Column::ALL {
+                        parent
+                            .spawn_bundle(ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(column.width()), Val::Px(32.0)),
+                                    align_items: AlignItems::Center,
+                                    padding: UiRect::all(Val::Px(4.0)),
+                                    ..default()
+                                },
+                                color: BUTTON_DEFAULT,
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn_bundle(TextBundle::from_section(
+                                    column.name(),
+                                    TextStyle {
+                                        font: fonts.main.clone(),
+                                        font_size: 18.0,
+                                        color: Color::WHITE,
+                                    },
+                                ));
+                            })
+                            .insert(SortButton(column));
+                    }
+                    parent.spawn_bundle(NodeBundle {
+                        color: TRANSPARENT,
+                        style: Style {
+                            size: Size::new(Val::Px(90.0), Val::Px(32.0)),
+                            ..default()
+                        },
+                        ..default()
+                    });
+                });
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: TRANSPARENT,
+                    style: Style {
+                        flex_direction: FlexDirection::ColumnReverse,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .insert(RecordRows);
+            parent.spawn_bundle(TextBundle::from_section(
+                "Click a column to sort, Esc to go back",
+                TextStyle {
+                    font: fonts.main.clone(),
+                    font_size: 18.0,
+                    color: Color::GRAY,
+                },
+            ));
+        });
+}
+
+fn sort_records(
+    mut sort: ResMut<RecordSort>,
+    buttons: Query<(&Interaction, &SortButton), Changed<Interaction>>,
+) {
+    for (interaction, SortButton(column)) in &buttons {
+        if *interaction == Interaction::Clicked {
+            if sort.column == *column {
+                sort.descending = !sort.descending;
+            } else {
+                sort.column = *column;
+                sort.descending = true;
+            }
+        }
+    }
+}
+
+fn show_records(
+    mut commands: Commands,
+    fonts: Res<Fonts>,
+    sort: Res<RecordSort>,
+    mut records: ResMut<Records>,
+    rows: Query<Entity, With<RecordRows>>,
+    sort_labels: Query<(&SortButton, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    if !sort.is_changed() {
+        return;
+    }
+    let rows = match rows.get_single() {
+        Ok(rows) => rows,
+        Err(_) => return,
+    };
+
+    for (SortButton(column), children) in &sort_labels {
+        if let Some(mut text) = children
+            .first()
+            .and_then(|child| texts.get_mut(*child).ok())
+        {
+            text.sections[0].value = if *column != sort.column {
+                column.name().to_string()
+            } else if sort.descending {
+                format!("{} v", column.name())
+            } else {
+                format!("{} ^", column.name())
+            };
+        }
+    }
+
+    records.0.sort_by(|a, b| {
+        let ordering = a.compare(b, sort.column);
+        if sort.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+
+    let text_style = TextStyle {
+        font: fonts.main.clone(),
+        font_size: 16.0,
+        color: Color::WHITE,
+    };
+    commands.entity(rows).despawn_descendants();
+    commands.entity(rows).with_children(|parent| {
+        if records.0.is_empty() {
+            parent.spawn_bundle(
+                TextBundle::from_section("No runs recorded yet", text_style.clone()).with_style(
+                    Style {
+                        margin: UiRect::all(Val::Px(12.0)),
+                        ..default()
+                    },
+                ),
+            );
+        }
+        for record in records.0.iter().take(MAX_RECORD_ROWS) {
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: UiColor(Color::rgba(0.0, 0.0, 0.0, 0.5)),
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        margin: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    for column in Column::ALL {
+                        row.spawn_bundle(NodeBundle {
+                            color: TRANSPARENT,
+                            style: Style {
+                                size: Size::new(Val::Px(column.width()), Val::Auto),
+                                padding: UiRect::all(Val::Px(4.0)),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|cell| {
+                            cell.spawn_bundle(TextBundle::from_section(
+                                record.cell(column),
+                                text_style.clone(),
+                            ));
+                        });
+                    }
+                    row.spawn_bundle(ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(90.0), Val::Px(28.0)),
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        color: BUTTON_DEFAULT,
+                        ..default()
+                    })
+                    .with_children(|button| {
+                        button.spawn_bundle(TextBundle::from_section("Replay", text_style.clone()));
+                    })
+                    .insert(ReplayButton(record.seed, record.class));
+                });
+        }
+    });
+}
+
+fn replay_seed(
+    mut commands: Commands,
+    mut pending_seed: ResMut<PendingSeed>,
+    buttons: Query<(&Interaction, &ReplayButton), Changed<Interaction>>,
+) {
+    for (interaction, ReplayButton(seed, class)) in &buttons {
+        if *interaction == Interaction::Clicked {
+            pending_seed.0 = Some(*seed);
+            commands.insert_resource(*class);
+            commands.insert_resource(NextState(GameState::InGame));
+        }
+    }
+}
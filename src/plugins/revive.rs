@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::{FillMode, *};
+use iyes_loopless::prelude::*;
+
+use crate::{
+    components::{
+        AllyType, AnimationController, Corpse, HasHealthBar, Health, InParty, IsDead, Player,
+        PlayerClass, Revivable, ReviveMarker,
+    },
+    consts::{ALCHEMIST_REVIVE_RANGE, REVIVE_HEALTH, REVIVE_RANGE, REVIVE_TIME},
+    GameState,
+};
+
+pub struct RevivePlugin;
+
+impl Plugin for RevivePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::InGame)
+                .with_system(channel_revives)
+                .with_system(update_revive_markers)
+                .into(),
+        );
+    }
+}
+
+fn channel_revives(
+    mut commands: Commands,
+    time: Res<Time>,
+    player: Query<(&Transform, &PlayerClass), (With<Player>, Without<IsDead>)>,
+    alchemists: Query<(&Transform, &AllyType), (With<InParty>, Without<IsDead>)>,
+    mut corpses: Query<
+        (
+            Entity,
+            &mut Revivable,
+            &mut Health,
+            &mut Transform,
+            &mut TextureAtlasSprite,
+            &mut AnimationController,
+            &AllyType,
+        ),
+        (With<IsDead>, Without<Player>),
+    >,
+) {
+    let (player_transform, player_class) = match player.get_single() {
+        Ok(player) => player,
+        Err(_) => return,
+    };
+    let player_pos = player_transform.translation.truncate();
+    let mut revivers = alchemists
+        .iter()
+        .filter(|(_, ally_type)| ally_type.revives())
+        .map(|(transform, _)| transform.translation.truncate())
+        .collect::<Vec<_>>();
+    if *player_class == PlayerClass::Alchemist {
+        revivers.push(player_pos);
+    }
+
+    for (entity, mut revivable, mut health, mut transform, mut sprite, mut controller, ally_type) in
+        &mut corpses
+    {
+        let pos = transform.translation.truncate();
+        if pos.distance(player_pos) <= REVIVE_RANGE {
+            revivable.progress += time.delta_seconds() / REVIVE_TIME;
+        } else if revivers
+            .iter()
+            .any(|reviver| reviver.distance(pos) <= ALCHEMIST_REVIVE_RANGE)
+        {
+            revivable.progress += time.delta_seconds() / (REVIVE_TIME * 2.0);
+        } else {
+            revivable.progress = 0.0;
+        }
+
+        if revivable.progress >= 1.0 {
+            health.0 = health.1 * REVIVE_HEALTH;
+            transform.rotation = Quat::IDENTITY;
+            sprite.color.set_a(1.0);
+            *controller = AnimationController::new(controller.set);
+            commands
+                .entity(entity)
+                .remove::<IsDead>()
+                .remove::<Corpse>()
+                .remove::<Revivable>()
+                .remove::<HasHealthBar>()
+                .insert(ally_type.collider())
+                .despawn_descendants();
+        }
+    }
+}
+
+fn update_revive_markers(
+    corpses: Query<&Revivable>,
+    mut markers: Query<(&Parent, &mut DrawMode), With<ReviveMarker>>,
+) {
+    for (parent, mut draw_mode) in &mut markers {
+        if let Ok(revivable) = corpses.get(parent.get()) {
+            *draw_mode = DrawMode::Outlined {
+                fill_mode: FillMode::color(Color::rgba(0.0, 1.0, 0.0, revivable.progress * 0.6)),
+                outline_mode: StrokeMode::new(Color::GREEN, 0.75),
+            };
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use iyes_loopless::prelude::*;
+
+use crate::{
+    components::{
+        AllyType, AttackRange, AttackTimer, Damage, DamageDealt, Health, InParty, Kills, Player,
+        RosterHighlight,
+    },
+    consts::TRANSPARENT,
+    helpers::despawn_with,
+    resources::Fonts,
+    GameState,
+};
+
+#[derive(Component)]
+struct Roster;
+
+#[derive(Component)]
+struct RosterRow(Entity);
+
+#[derive(Component)]
+struct RosterRowText(Entity);
+
+pub struct RosterPlugin;
+
+impl Plugin for RosterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(GameState::InGame, spawn_roster)
+            .add_exit_system(GameState::InGame, despawn_with::<Roster>)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::InGame)
+                    .with_system(toggle_roster)
+                    .with_system(rebuild_roster_rows)
+                    .with_system(update_roster_text)
+                    .with_system(highlight_hovered_member)
+                    .into(),
+            );
+    }
+}
+
+fn spawn_roster(mut commands: Commands) {
+    commands
+        .spawn_bundle(NodeBundle {
+            color: UiColor(Color::rgba(0.0, 0.0, 0.0, 0.7)),
+            style: Style {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(130.0),
+                    right: Val::Px(10.0),
+                    ..default()
+                },
+                flex_direction: FlexDirection::ColumnReverse,
+                padding: UiRect::all(Val::Px(5.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(Roster);
+}
+
+fn toggle_roster(keyboard: Res<Input<KeyCode>>, mut roster: Query<&mut Style, With<Roster>>) {
+    if !keyboard.just_pressed(KeyCode::Tab) {
+        return;
+    }
+    for mut style in &mut roster {
+        style.display = match style.display {
+            Display::Flex => Display::None,
+            Display::None => Display::Flex,
+        };
+    }
+}
+
+fn rebuild_roster_rows(
+    mut commands: Commands,
+    fonts: Res<Fonts>,
+    texture_atlases: Res<Assets<TextureAtlas>>,
+    mut current: Local<Vec<Entity>>,
+    roster: Query<Entity, With<Roster>>,
+    party_members: Query<(Entity, &Handle<TextureAtlas>), (With<InParty>, Without<Player>)>,
+) {
+    let roster = match roster.get_single() {
+        Ok(roster) => roster,
+        Err(_) => return,
+    };
+    let mut members = party_members
+        .iter()
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    members.sort();
+    if *current == members {
+        return;
+    }
+    *current = members;
+
+    commands.entity(roster).despawn_descendants();
+    commands.entity(roster).with_children(|parent| {
+        for &entity in current.iter() {
+            let (_, atlas_handle) = party_members.get(entity).unwrap();
+            let atlas = texture_atlases.get(atlas_handle);
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: TRANSPARENT,
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        margin: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .insert(Interaction::default())
+                .insert(RosterRow(entity))
+                .with_children(|row| {
+                    row.spawn_bundle(NodeBundle {
+                        color: TRANSPARENT,
+                        style: Style {
+                            size: Size::new(Val::Px(32.0), Val::Px(32.0)),
+                            overflow: Overflow::Hidden,
+                            margin: UiRect {
+                                right: Val::Px(8.0),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|icon| {
+                        if let Some(atlas) = atlas {
+                            let tile = atlas.textures[0].size();
+                            let scale = 32.0 / tile.y;
+                            icon.spawn_bundle(ImageBundle {
+                                image: UiImage(atlas.texture.clone()),
+                                style: Style {
+                                    size: Size::new(
+                                        Val::Px(atlas.size.x * scale),
+                                        Val::Px(atlas.size.y * scale),
+                                    ),
+                                    flex_shrink: 0.0,
+                                    ..default()
+                                },
+                                ..default()
+                            });
+                        }
+                    });
+                    row.spawn_bundle(TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: fonts.main.clone(),
+                            font_size: 18.0,
+                            color: Color::WHITE,
+                        },
+                    ))
+                    .insert(RosterRowText(entity));
+                });
+        }
+    });
+}
+
+fn update_roster_text(
+    members: Query<(
+        &AllyType,
+        &Health,
+        &Damage,
+        &AttackRange,
+        &AttackTimer,
+        &Kills,
+        &DamageDealt,
+    )>,
+    mut texts: Query<(&RosterRowText, &mut Text)>,
+) {
+    for (row, mut text) in &mut texts {
+        if let Ok((ally_type, health, damage, range, timer, kills, dealt)) = members.get(row.0) {
+            let health_text = if health.0 > 0.0 {
+                format!("{:.0}/{:.0}", health.0, health.1)
+            } else {
+                "down".to_string()
+            };
+            text.sections[0].value = format!(
+                "{:<9} HP {:<7} DMG {:<3.0} RNG {:<3.0} SPD {:.2}/s  Kills {:<3} Dealt {:.0}",
+                ally_type.name(),
+                health_text,
+                damage.0,
+                range.0,
+                1.0 / timer.duration().as_secs_f32(),
+                kills.0,
+                dealt.0
+            );
+        }
+    }
+}
+
+fn highlight_hovered_member(
+    mut commands: Commands,
+    mut highlighted: Local<Option<Entity>>,
+    rows: Query<(&RosterRow, &Interaction)>,
+    highlights: Query<Entity, With<RosterHighlight>>,
+    units: Query<(), With<InParty>>,
+) {
+    let hovered = rows
+        .iter()
+        .find(|(_, interaction)| **interaction != Interaction::None)
+        .map(|(row, _)| row.0)
+        .filter(|entity| units.contains(*entity));
+    if hovered == *highlighted {
+        return;
+    }
+    *highlighted = hovered;
+
+    for entity in &highlights {
+        commands.entity(entity).despawn_recursive();
+    }
+    if let Some(entity) = hovered {
+        let shape = shapes::Circle {
+            radius: 11.0,
+            ..default()
+        };
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn_bundle(GeometryBuilder::build_as(
+                    &shape,
+                    DrawMode::Stroke(StrokeMode::new(Color::GOLD, 1.0)),
+                    Transform::from_translation(Vec3::Z * 15.0),
+                ))
+                .insert(RosterHighlight);
+        });
+    }
+}
@@ -1,12 +1,12 @@
 use crate::{
     components::{
-        AllyBundle, AnimationTimer, AttackRange, AttackTimer, Damage, EnemyBundle, Health,
+        AllyBundle, AnimationController, AttackRange, AttackTimer, Damage, EnemyBundle, Health,
         PartyRadius, Player, Speed,
     },
-    consts::{SPRITE_SCALE, XEXTENT, YEXTENT},
+    consts::{ALLY_GROUPS, SPRITE_SCALE, UNIT_DAMPING, XEXTENT, YEXTENT},
     resources::{
         AllySpawnTimer, DifficultyScaleTimer, EnemyScale, EnemySpawnChance, EnemySpawnTimer,
-        Sprites,
+        SpawnRng, Sprites,
     },
     AllyType, EnemyType, GameState,
 };
@@ -18,11 +18,13 @@ pub struct SpawnPlugin;
 
 impl Plugin for SpawnPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(EnemySpawnTimer(Timer::from_seconds(0.75, true)))
+        app.insert_resource(SpawnRng::from_seed(random()))
+            .insert_resource(EnemySpawnTimer(Timer::from_seconds(0.75, true)))
             .insert_resource(AllySpawnTimer(Timer::from_seconds(1.0, true)))
             .insert_resource(DifficultyScaleTimer(Timer::from_seconds(1.5, true)))
             .insert_resource(EnemySpawnChance(0.8))
             .insert_resource(EnemyScale(1.0))
+            .add_enter_system(GameState::InGame, reset_spawn_timers)
             .add_system_set(
                 ConditionSet::new()
                     .run_in_state(GameState::InGame)
@@ -34,6 +36,16 @@ impl Plugin for SpawnPlugin {
     }
 }
 
+fn reset_spawn_timers(
+    mut enemy_timer: ResMut<EnemySpawnTimer>,
+    mut ally_timer: ResMut<AllySpawnTimer>,
+    mut difficulty_timer: ResMut<DifficultyScaleTimer>,
+) {
+    enemy_timer.reset();
+    ally_timer.reset();
+    difficulty_timer.reset();
+}
+
 fn scale_difficulty(
     time: Res<Time>,
     mut difficulty_timer: ResMut<DifficultyScaleTimer>,
@@ -53,13 +65,15 @@ fn spawn_allies(
     time: Res<Time>,
     player: Query<(&Transform, &PartyRadius), With<Player>>,
     mut spawn_timer: ResMut<AllySpawnTimer>,
+    mut spawn_rng: ResMut<SpawnRng>,
 ) {
     spawn_timer.tick(time.delta());
 
     if spawn_timer.just_finished() {
-        let mut rng = rand::thread_rng();
-        let roll: f32 = rng.gen();
+        let roll: f32 = spawn_rng.allies.gen();
+        let mut rng = StdRng::seed_from_u64(spawn_rng.allies.gen());
         if roll >= 0.8 {
+            let ally_type: AllyType = rng.gen();
             let (player_transform, party_radius) = player.single();
             const MAX_TRIES: u32 = 100;
             let mut i = 0;
@@ -86,95 +100,33 @@ fn spawn_allies(
                 }
             };
 
-            let mut timer = Timer::from_seconds(0.115, true);
-            timer.pause();
-
-            let ally_type: AllyType = rng.gen();
-
-            match ally_type {
-                AllyType::Alchemist => commands.spawn_bundle(AllyBundle {
-                    ally_type,
-                    health: Health(100.0, 100.0),
-                    damage: Damage(15.0),
-                    attack_range: AttackRange(80.),
-                    attack_timer: AttackTimer(Timer::from_seconds(1.0, true)),
-                    sprite: SpriteSheetBundle {
-                        texture_atlas: sprites.alchemist.clone(),
-                        transform,
-                        ..default()
-                    },
-                    ..default()
-                }),
-                AllyType::Archer => commands.spawn_bundle(AllyBundle {
-                    ally_type,
-                    health: Health(100.0, 100.0),
-                    damage: Damage(15.0),
-                    attack_range: AttackRange(250.),
-                    attack_timer: AttackTimer(Timer::from_seconds(0.5, true)),
-                    sprite: SpriteSheetBundle {
-                        texture_atlas: sprites.archer.clone(),
-                        transform,
-                        ..default()
-                    },
-                    ..default()
-                }),
-                AllyType::Cyclops => commands.spawn_bundle(AllyBundle {
-                    ally_type,
-                    health: Health(150.0, 150.0),
-                    damage: Damage(35.0),
-                    attack_range: AttackRange(60.),
-                    attack_timer: AttackTimer(Timer::from_seconds(1.5, true)),
-                    sprite: SpriteSheetBundle {
-                        texture_atlas: sprites.cyclops.clone(),
-                        transform,
-                        ..default()
-                    },
-                    ..default()
-                }),
-                AllyType::Dwarf => commands.spawn_bundle(AllyBundle {
-                    ally_type,
-                    health: Health(90.0, 90.0),
-                    damage: Damage(25.0),
-                    attack_range: AttackRange(90.),
-                    attack_timer: AttackTimer(Timer::from_seconds(1.0, true)),
-                    sprite: SpriteSheetBundle {
-                        texture_atlas: sprites.dwarf.clone(),
-                        transform,
-                        ..default()
-                    },
-                    ..default()
-                }),
-                AllyType::Knight => commands.spawn_bundle(AllyBundle {
-                    ally_type,
-                    health: Health(115.0, 115.0),
-                    damage: Damage(25.0),
-                    attack_range: AttackRange(80.),
-                    attack_timer: AttackTimer(Timer::from_seconds(0.75, true)),
-                    sprite: SpriteSheetBundle {
-                        texture_atlas: sprites.knight.clone(),
-                        transform,
-                        ..default()
-                    },
-                    ..default()
-                }),
-                AllyType::Wizard => commands.spawn_bundle(AllyBundle {
+            let stats = match ally_type.stats() {
+                Some(stats) => stats,
+                None => return,
+            };
+            commands
+                .spawn_bundle(AllyBundle {
                     ally_type,
-                    health: Health(75.0, 75.0),
-                    damage: Damage(30.0),
-                    attack_range: AttackRange(200.),
-                    attack_timer: AttackTimer(Timer::from_seconds(1.25, true)),
+                    health: Health(stats.health, stats.health),
+                    damage: Damage(stats.damage),
+                    attack_range: AttackRange(stats.attack_range),
+                    attack_timer: AttackTimer(Timer::from_seconds(stats.attack_cooldown, true)),
                     sprite: SpriteSheetBundle {
-                        texture_atlas: sprites.wizard.clone(),
+                        texture_atlas: sprites.ally(ally_type),
                         transform,
                         ..default()
                     },
                     ..default()
-                }),
-                AllyType::Player => unreachable!(),
-            }
-            .insert(AnimationTimer(timer))
-            .insert(Collider::cuboid(8.0, 8.0))
-            .insert(LockedAxes::ROTATION_LOCKED);
+                })
+                .insert(AnimationController::new(ally_type.animations()))
+                .insert(ally_type.collider())
+                .insert(ALLY_GROUPS)
+                .insert(ColliderMassProperties::Density(ally_type.density()))
+                .insert(Damping {
+                    linear_damping: UNIT_DAMPING,
+                    angular_damping: 0.0,
+                })
+                .insert(LockedAxes::ROTATION_LOCKED);
         }
     }
 }
@@ -186,14 +138,16 @@ fn spawn_wave(
     enemy_spawn_chance: Res<EnemySpawnChance>,
     enemy_scale: Res<EnemyScale>,
     mut spawn_timer: ResMut<EnemySpawnTimer>,
+    mut spawn_rng: ResMut<SpawnRng>,
     player: Query<(&Transform, &PartyRadius), With<Player>>,
 ) {
     spawn_timer.tick(time.delta());
 
     if spawn_timer.just_finished() {
-        let mut rng = rand::thread_rng();
-        let roll: f32 = rng.gen();
+        let roll: f32 = spawn_rng.enemies.gen();
+        let mut rng = StdRng::seed_from_u64(spawn_rng.enemies.gen());
         if roll >= enemy_spawn_chance.0 {
+            let enemy_type: EnemyType = rng.gen();
             let (player_transform, party_radius) = player.single();
             const MAX_TRIES: u32 = 100;
             let mut i = 0;
@@ -220,96 +174,32 @@ fn spawn_wave(
                 }
             };
 
-            let enemy_type: EnemyType = rng.gen();
-            match enemy_type {
-                EnemyType::Bat => commands.spawn_bundle(EnemyBundle {
-                    enemy_type,
-                    speed: Speed(90.0),
-                    health: Health(60.0 * enemy_scale.0, 60.0 * enemy_scale.0),
-                    damage: Damage(8.0 * enemy_scale.0),
-                    attack_range: AttackRange(45.),
-                    attack_timer: AttackTimer(Timer::from_seconds(0.75, true)),
-                    sprite: SpriteSheetBundle {
-                        texture_atlas: sprites.bat.clone(),
-                        transform,
-                        ..default()
-                    },
-                    ..default()
-                }),
-                EnemyType::EvilWizard => commands.spawn_bundle(EnemyBundle {
-                    enemy_type,
-                    speed: Speed(70.0),
-                    health: Health(75.0 * enemy_scale.0, 75.0 * enemy_scale.0),
-                    damage: Damage(15. * enemy_scale.0),
-                    attack_range: AttackRange(200.),
-                    attack_timer: AttackTimer(Timer::from_seconds(1.25, true)),
-                    sprite: SpriteSheetBundle {
-                        texture_atlas: sprites.evil_wizard.clone(),
-                        transform,
-                        ..default()
-                    },
-                    ..default()
-                }),
-                EnemyType::Ghost => commands.spawn_bundle(EnemyBundle {
-                    enemy_type,
-                    speed: Speed(65.0),
-                    health: Health(100.0 * enemy_scale.0, 100.0 * enemy_scale.0),
-                    damage: Damage(20. * enemy_scale.0),
-                    attack_range: AttackRange(60.),
-                    attack_timer: AttackTimer(Timer::from_seconds(1.0, true)),
-                    sprite: SpriteSheetBundle {
-                        texture_atlas: sprites.ghost.clone(),
-                        transform,
-                        ..default()
-                    },
-                    ..default()
-                }),
-                EnemyType::Lobster => commands.spawn_bundle(EnemyBundle {
-                    enemy_type,
-                    speed: Speed(75.0),
-                    health: Health(80.0 * enemy_scale.0, 80.0 * enemy_scale.0),
-                    damage: Damage(15. * enemy_scale.0),
-                    attack_range: AttackRange(40.),
-                    attack_timer: AttackTimer(Timer::from_seconds(1.0, true)),
-                    sprite: SpriteSheetBundle {
-                        texture_atlas: sprites.lobster.clone(),
-                        transform,
-                        ..default()
-                    },
-                    ..default()
-                }),
-                EnemyType::Rat => commands.spawn_bundle(EnemyBundle {
-                    enemy_type,
-                    speed: Speed(110.0),
-                    health: Health(50.0 * enemy_scale.0, 50.0 * enemy_scale.0),
-                    damage: Damage(5. * enemy_scale.0),
-                    attack_range: AttackRange(50.),
-                    attack_timer: AttackTimer(Timer::from_seconds(0.5, true)),
-                    sprite: SpriteSheetBundle {
-                        texture_atlas: sprites.rat.clone(),
-                        transform,
-                        ..default()
-                    },
-                    ..default()
-                }),
-                EnemyType::Spider => commands.spawn_bundle(EnemyBundle {
+            let stats = enemy_type.stats();
+            let health = stats.health * enemy_scale.0;
+            commands
+                .spawn_bundle(EnemyBundle {
                     enemy_type,
-                    speed: Speed(150.0),
-                    health: Health(65.0 * enemy_scale.0, 65.0 * enemy_scale.0),
-                    damage: Damage(10. * enemy_scale.0),
-                    attack_range: AttackRange(40.),
-                    attack_timer: AttackTimer(Timer::from_seconds(0.75, true)),
+                    speed: Speed(enemy_type.speed()),
+                    health: Health(health, health),
+                    damage: Damage(stats.damage * enemy_scale.0),
+                    attack_range: AttackRange(stats.attack_range),
+                    attack_timer: AttackTimer(Timer::from_seconds(stats.attack_cooldown, true)),
                     sprite: SpriteSheetBundle {
-                        texture_atlas: sprites.spider.clone(),
+                        texture_atlas: sprites.enemy(enemy_type),
                         transform,
                         ..default()
                     },
                     ..default()
-                }),
-            }
-            .insert(AnimationTimer(Timer::from_seconds(0.115, true)))
-            .insert(Collider::cuboid(8.0, 8.0))
-            .insert(LockedAxes::ROTATION_LOCKED);
+                })
+                .insert(AnimationController::new(enemy_type.animations()))
+                .insert(enemy_type.collider())
+                .insert(enemy_type.collision_groups())
+                .insert(ColliderMassProperties::Density(enemy_type.density()))
+                .insert(Damping {
+                    linear_damping: UNIT_DAMPING,
+                    angular_damping: 0.0,
+                })
+                .insert(LockedAxes::ROTATION_LOCKED);
         }
     }
 }
@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use iyes_loopless::prelude::*;
+
+use crate::{
+    components::{EnemyType, FocusTarget, IsDead, MainCamera, Player, Reticle},
+    helpers::cursor_world_position,
+    GameState,
+};
+
+pub struct TargetingPlugin;
+
+impl Plugin for TargetingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            ConditionSet::new()
+                .run_in_state(GameState::InGame)
+                .with_system(mark_focus_target)
+                .with_system(rotate_reticles)
+                .into(),
+        );
+    }
+}
+
+fn mark_focus_target(
+    mut commands: Commands,
+    windows: Res<Windows>,
+    mouse: Res<Input<MouseButton>>,
+    keyboard: Res<Input<KeyCode>>,
+    camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    player: Query<&Transform, With<Player>>,
+    enemies: Query<(Entity, &Transform), (With<EnemyType>, Without<IsDead>)>,
+    focused: Query<(Entity, &Children), With<FocusTarget>>,
+    reticles: Query<(), With<Reticle>>,
+) {
+    let current = focused.get_single().ok().map(|(entity, _)| entity);
+
+    let new_target = if mouse.just_pressed(MouseButton::Left) {
+        let cursor = match camera.get_single() {
+            Ok((camera, camera_transform)) => {
+                cursor_world_position(&windows, camera, camera_transform)
+            }
+            Err(_) => None,
+        };
+        let clicked = cursor.and_then(|cursor| {
+            enemies
+                .iter()
+                .find(|(_, transform)| transform.translation.truncate().distance(cursor) < 16.0)
+                .map(|(entity, _)| entity)
+        });
+        match clicked {
+            Some(entity) if Some(entity) == current => None,
+            Some(entity) => Some(entity),
+            None => return,
+        }
+    } else if keyboard.just_pressed(KeyCode::T) {
+        let player_pos = player.single().translation.truncate();
+        let mut by_distance = enemies
+            .iter()
+            .map(|(entity, transform)| {
+                (
+                    entity,
+                    transform.translation.truncate().distance(player_pos),
+                )
+            })
+            .collect::<Vec<_>>();
+        by_distance.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let next = current
+            .and_then(|current| {
+                by_distance
+                    .iter()
+                    .position(|(entity, _)| *entity == current)
+            })
+            .map_or(0, |i| i + 1);
+        by_distance
+            .get(next)
+            .or_else(|| by_distance.first())
+            .map(|(entity, _)| *entity)
+    } else {
+        return;
+    };
+
+    if let Ok((entity, children)) = focused.get_single() {
+        for &child in children {
+            if reticles.contains(child) {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+        commands.entity(entity).remove::<FocusTarget>();
+    }
+
+    if let Some(entity) = new_target {
+        let circle = shapes::Circle {
+            radius: 10.0,
+            ..default()
+        };
+        let horizontal = shapes::Line(Vec2::new(-13.0, 0.0), Vec2::new(13.0, 0.0));
+        let vertical = shapes::Line(Vec2::new(0.0, -13.0), Vec2::new(0.0, 13.0));
+        commands
+            .entity(entity)
+            .insert(FocusTarget)
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(
+                        GeometryBuilder::new()
+                            .add(&circle)
+                            .add(&horizontal)
+                            .add(&vertical)
+                            .build(
+                                DrawMode::Stroke(StrokeMode {
+                                    color: Color::ORANGE_RED,
+                                    options: StrokeOptions::default().with_line_width(0.75),
+                                }),
+                                Transform::from_translation(Vec3::Z * 15.0),
+                            ),
+                    )
+                    .insert(Reticle);
+            });
+    }
+}
+
+fn rotate_reticles(time: Res<Time>, mut reticles: Query<&mut Transform, With<Reticle>>) {
+    for mut transform in &mut reticles {
+        transform.rotate_z(time.delta_seconds());
+    }
+}
@@ -0,0 +1,196 @@
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+
+use crate::{
+    components::{
+        AllyType, AttackRange, AttackTimer, Damage, EnemyType, FocusTarget, Health, InParty,
+        Invulnerable, IsDead, Kills, MainCamera, PinnedOffset, PlayerClass, Rallied, Revivable,
+        Speed,
+    },
+    consts::TOOLTIP_PICK_RADIUS,
+    helpers::{cursor_world_position, despawn_with},
+    resources::Fonts,
+    GameState,
+};
+
+#[derive(Component)]
+struct Tooltip;
+
+#[derive(Component)]
+struct TooltipText;
+
+pub struct TooltipPlugin;
+
+impl Plugin for TooltipPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_enter_system(GameState::InGame, spawn_tooltip)
+            .add_exit_system(GameState::InGame, despawn_with::<Tooltip>)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::InGame)
+                    .with_system(update_tooltip)
+                    .into(),
+            );
+    }
+}
+
+fn spawn_tooltip(mut commands: Commands, fonts: Res<Fonts>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            color: UiColor(Color::rgba(0.0, 0.0, 0.0, 0.8)),
+            style: Style {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                padding: UiRect::all(Val::Px(6.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(Tooltip)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: fonts.main.clone(),
+                        font_size: 18.0,
+                        color: Color::WHITE,
+                    },
+                ))
+                .insert(TooltipText);
+        });
+}
+
+fn update_tooltip(
+    windows: Res<Windows>,
+    camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    units: Query<
+        (
+            &Transform,
+            &Health,
+            &Damage,
+            &AttackRange,
+            &AttackTimer,
+            Option<&AllyType>,
+            Option<&EnemyType>,
+            Option<&PlayerClass>,
+            Option<&Speed>,
+            Option<&Kills>,
+            (
+                Option<&InParty>,
+                Option<&IsDead>,
+                Option<&Revivable>,
+                Option<&Rallied>,
+                Option<&Invulnerable>,
+                Option<&FocusTarget>,
+                Option<&PinnedOffset>,
+            ),
+        ),
+        Or<(With<AllyType>, With<EnemyType>)>,
+    >,
+    mut tooltip: Query<&mut Style, With<Tooltip>>,
+    mut text: Query<&mut Text, With<TooltipText>>,
+) {
+    let (mut style, mut text) = match (tooltip.get_single_mut(), text.get_single_mut()) {
+        (Ok(style), Ok(text)) => (style, text),
+        _ => return,
+    };
+    style.display = Display::None;
+
+    let cursor = match (windows.get_primary(), camera.get_single()) {
+        (Some(window), Ok((camera, camera_transform))) => window
+            .cursor_position()
+            .zip(cursor_world_position(&windows, camera, camera_transform)),
+        _ => None,
+    };
+    let (screen_cursor, world_cursor) = match cursor {
+        Some(cursor) => cursor,
+        None => return,
+    };
+
+    let hovered = units
+        .iter()
+        .map(|unit| (unit.0.translation.truncate().distance(world_cursor), unit))
+        .filter(|(dist, _)| *dist <= TOOLTIP_PICK_RADIUS)
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, unit)| unit);
+    let (
+        _,
+        health,
+        damage,
+        range,
+        timer,
+        maybe_ally,
+        maybe_enemy,
+        maybe_class,
+        maybe_speed,
+        maybe_kills,
+        (in_party, dead, revivable, rallied, invulnerable, focused, pinned),
+    ) = match hovered {
+        Some(unit) => unit,
+        None => return,
+    };
+
+    let name = match (maybe_class, maybe_ally, maybe_enemy) {
+        (Some(class), ..) => format!("Player ({})", class.name()),
+        (None, Some(ally_type), _) if in_party.is_some() => ally_type.name().to_string(),
+        (None, Some(ally_type), _) => format!("{} (recruit)", ally_type.name()),
+        (None, None, Some(enemy_type)) => enemy_type.name().to_string(),
+        _ => return,
+    };
+    let mut lines = vec![
+        name,
+        format!("HP {:.0}/{:.0}", health.0.max(0.0), health.1),
+        format!(
+            "DMG {:.0}  RNG {:.0}  SPD {:.2}/s",
+            damage.0,
+            range.0,
+            1.0 / timer.duration().as_secs_f32()
+        ),
+    ];
+    if let Some(speed) = maybe_speed {
+        lines.push(format!("Move {:.0}", speed.0));
+    }
+    if let Some(kills) = maybe_kills.filter(|kills| kills.0 > 0) {
+        lines.push(format!("Kills {}", kills.0));
+    }
+
+    let mut effects = Vec::new();
+    if let Some(revivable) = revivable {
+        effects.push(format!("Down, reviving {:.0}%", revivable.progress * 100.0));
+    } else if dead.is_some() {
+        effects.push("Dead".to_string());
+    }
+    if let Some(rallied) = rallied {
+        effects.push(format!(
+            "Rallied {:.1}s",
+            rallied.duration().as_secs_f32() - rallied.elapsed_secs()
+        ));
+    }
+    if invulnerable.is_some() {
+        effects.push("Invulnerable".to_string());
+    }
+    if focused.is_some() {
+        effects.push("Focused".to_string());
+    }
+    if pinned.is_some() {
+        effects.push("Pinned".to_string());
+    }
+    if maybe_ally
+        .zip(maybe_kills)
+        .is_some_and(|(ally, kills)| ally.piercing(kills.0) > 0)
+    {
+        effects.push("Veteran: piercing arrows".to_string());
+    }
+    if !effects.is_empty() {
+        lines.push(effects.join(", "));
+    }
+
+    text.sections[0].value = lines.join("\n");
+    style.display = Display::Flex;
+    style.position = UiRect {
+        left: Val::Px(screen_cursor.x + 16.0),
+        bottom: Val::Px(screen_cursor.y + 16.0),
+        ..default()
+    };
+}
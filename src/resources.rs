@@ -1,5 +1,8 @@
 use bevy::{audio::AudioSink, prelude::*};
 use bevy_asset_loader::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::components::{AllyType, AnimationSet, Clip, EnemyType};
 
 #[derive(AssetCollection)]
 pub struct Sprites {
@@ -54,12 +57,145 @@ pub struct Sprites {
     #[asset(texture_atlas(tile_size_x = 16., tile_size_y = 16., columns = 14, rows = 1))]
     #[asset(path = "sprites/playerdeath-sheet.png")]
     pub player_death: Handle<TextureAtlas>,
-    #[asset(path = "sprites/character_info.png")]
-    pub info: Handle<Image>,
     #[asset(path = "sprites/background.png")]
     pub background: Handle<Image>,
 }
 
+impl Sprites {
+    pub fn ally(&self, ally_type: AllyType) -> Handle<TextureAtlas> {
+        match ally_type {
+            AllyType::Alchemist => self.alchemist.clone(),
+            AllyType::Archer => self.archer.clone(),
+            AllyType::Cyclops => self.cyclops.clone(),
+            AllyType::Dwarf => self.dwarf.clone(),
+            AllyType::Knight => self.knight.clone(),
+            AllyType::Wizard => self.wizard.clone(),
+            AllyType::Player => self.player.clone(),
+        }
+    }
+
+    pub fn enemy(&self, enemy_type: EnemyType) -> Handle<TextureAtlas> {
+        match enemy_type {
+            EnemyType::Bat => self.bat.clone(),
+            EnemyType::EvilWizard => self.evil_wizard.clone(),
+            EnemyType::Ghost => self.ghost.clone(),
+            EnemyType::Lobster => self.lobster.clone(),
+            EnemyType::Rat => self.rat.clone(),
+            EnemyType::Spider => self.spider.clone(),
+        }
+    }
+}
+
+// Animation sets for each sheet above
+pub const ALCHEMIST_ANIMATIONS: AnimationSet = CHARACTER_ANIMATIONS;
+pub const ARCHER_ANIMATIONS: AnimationSet = CHARACTER_ANIMATIONS;
+pub const CYCLOPS_ANIMATIONS: AnimationSet = AnimationSet {
+    walk: Clip {
+        frame_time: 0.16,
+        ..CHARACTER_ANIMATIONS.walk
+    },
+    ..CHARACTER_ANIMATIONS
+};
+pub const DWARF_ANIMATIONS: AnimationSet = CHARACTER_ANIMATIONS;
+pub const KNIGHT_ANIMATIONS: AnimationSet = CHARACTER_ANIMATIONS;
+pub const WIZARD_ANIMATIONS: AnimationSet = CHARACTER_ANIMATIONS;
+pub const BAT_ANIMATIONS: AnimationSet = AnimationSet {
+    idle: CHARACTER_ANIMATIONS.walk,
+    walk: Clip {
+        frame_time: 0.08,
+        ..CHARACTER_ANIMATIONS.walk
+    },
+    ..CHARACTER_ANIMATIONS
+};
+pub const EVIL_WIZARD_ANIMATIONS: AnimationSet = CHARACTER_ANIMATIONS;
+pub const LOBSTER_ANIMATIONS: AnimationSet = CHARACTER_ANIMATIONS;
+pub const RAT_ANIMATIONS: AnimationSet = CHARACTER_ANIMATIONS;
+pub const SPIDER_ANIMATIONS: AnimationSet = AnimationSet {
+    walk: Clip {
+        frame_time: 0.07,
+        ..CHARACTER_ANIMATIONS.walk
+    },
+    ..CHARACTER_ANIMATIONS
+};
+
+// Three frame sheets: frame 0 is idle, frames 1 and 2 are the walk cycle
+pub const CHARACTER_ANIMATIONS: AnimationSet = AnimationSet {
+    idle: Clip {
+        first: 0,
+        last: 0,
+        frame_time: 0.2,
+        looping: true,
+    },
+    walk: Clip {
+        first: 1,
+        last: 2,
+        frame_time: 0.115,
+        looping: true,
+    },
+    attack: Clip {
+        first: 1,
+        last: 2,
+        frame_time: 0.08,
+        looping: false,
+    },
+    hurt: Clip {
+        first: 0,
+        last: 0,
+        frame_time: 0.15,
+        looping: false,
+    },
+    die: Clip {
+        first: 0,
+        last: 0,
+        frame_time: 0.2,
+        looping: false,
+    },
+};
+
+pub const GHOST_ANIMATIONS: AnimationSet = AnimationSet {
+    idle: Clip {
+        first: 0,
+        last: 0,
+        frame_time: 0.2,
+        looping: true,
+    },
+    walk: Clip {
+        first: 0,
+        last: 0,
+        frame_time: 0.2,
+        looping: true,
+    },
+    attack: Clip {
+        first: 0,
+        last: 0,
+        frame_time: 0.16,
+        looping: false,
+    },
+    hurt: Clip {
+        first: 0,
+        last: 0,
+        frame_time: 0.15,
+        looping: false,
+    },
+    die: Clip {
+        first: 0,
+        last: 0,
+        frame_time: 0.2,
+        looping: false,
+    },
+};
+
+// Dying switches the player to playerdeath-sheet.png
+pub const PLAYER_ANIMATIONS: AnimationSet = AnimationSet {
+    die: Clip {
+        first: 0,
+        last: 13,
+        frame_time: 0.05,
+        looping: false,
+    },
+    ..CHARACTER_ANIMATIONS
+};
+
 #[derive(AssetCollection)]
 pub struct Sounds {
     #[asset(path = "sounds/arrowsound.wav")]
@@ -84,9 +220,59 @@ pub struct Fonts {
     pub main: Handle<Font>,
 }
 
-#[derive(Deref, DerefMut)]
+#[derive(Default, Deref, DerefMut)]
 pub struct DraggingEntity(pub Option<Entity>);
 
+#[derive(Default, Deref, DerefMut)]
+pub struct ShowColliders(pub bool);
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum Formation {
+    #[default]
+    Ring,
+    Line,
+    Wedge,
+    Ranks,
+}
+
+impl Formation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Formation::Ring => "Ring",
+            Formation::Line => "Line",
+            Formation::Wedge => "Wedge",
+            Formation::Ranks => "Ranks",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum Stance {
+    #[default]
+    Follow,
+    Aggressive,
+    Defensive,
+    Hold(Vec2),
+}
+
+impl Stance {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stance::Follow => "Follow",
+            Stance::Aggressive => "Aggressive",
+            Stance::Defensive => "Defensive",
+            Stance::Hold(_) => "Hold",
+        }
+    }
+
+    pub fn anchor(&self, player_pos: Vec2) -> Vec2 {
+        match self {
+            Stance::Hold(anchor) => *anchor,
+            _ => player_pos,
+        }
+    }
+}
+
 #[derive(Deref, DerefMut)]
 pub struct EnemySpawnTimer(pub Timer);
 
@@ -100,5 +286,35 @@ pub struct EnemySpawnChance(pub f32);
 
 pub struct EnemyScale(pub f32);
 
+pub struct SpawnRng {
+    pub allies: StdRng,
+    pub enemies: StdRng,
+}
+
+impl SpawnRng {
+    pub fn from_seed(seed: u64) -> Self {
+        let mut root = StdRng::seed_from_u64(seed);
+        Self {
+            allies: StdRng::seed_from_u64(root.gen()),
+            enemies: StdRng::seed_from_u64(root.gen()),
+        }
+    }
+}
+
+#[derive(Deref, DerefMut)]
+pub struct CombatRng(pub StdRng);
+
+#[derive(Default)]
+pub struct RunSeed(pub u64);
+
+#[derive(Default)]
+pub struct PendingSeed(pub Option<u64>);
+
+#[derive(Default)]
+pub struct RunStats {
+    pub elapsed: f32,
+    pub kills: u32,
+}
+
 #[derive(Default, Deref, DerefMut)]
 pub struct MusicController(pub Handle<AudioSink>);